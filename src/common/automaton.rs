@@ -0,0 +1,379 @@
+use std::hash::Hash;
+
+use hashbrown::hash_map::Entry;
+use hashbrown::HashMap;
+
+use super::{Direction, Grid, SparseGrid, Vec2};
+
+/// Which cells count as neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The four cardinal neighbors.
+    VonNeumann,
+    /// The four cardinal and four ordinal neighbors.
+    Moore,
+}
+
+impl Neighborhood {
+    fn directions(self) -> impl Iterator<Item = Direction> {
+        let all = self == Neighborhood::Moore;
+        Direction::all().filter(move |dir| all || dir.is_cardinal())
+    }
+}
+
+/// A space of cells that an [`Automaton`] can update.
+pub trait CellSpace: Clone + PartialEq {
+    type Cell: PartialEq;
+
+    /// The positions of all cells that may change in the next generation.
+    fn active(&self, neighborhood: Neighborhood) -> Vec<Vec2<isize>>;
+
+    /// The cell at `pos`, or `None` if it lies outside the space.
+    fn cell(&self, pos: Vec2<isize>) -> Option<&Self::Cell>;
+
+    fn cell_mut(&mut self, pos: Vec2<isize>) -> Option<&mut Self::Cell>;
+
+    fn set_cell(&mut self, pos: Vec2<isize>, value: Self::Cell);
+
+    /// A cheap hash of the whole space, used to detect cycles.
+    fn fingerprint(&self) -> u64;
+}
+
+impl<T: Clone + PartialEq + Hash> CellSpace for Grid<T> {
+    type Cell = T;
+
+    fn active(&self, _: Neighborhood) -> Vec<Vec2<isize>> {
+        self.coordinates().collect()
+    }
+
+    fn cell(&self, pos: Vec2<isize>) -> Option<&T> {
+        self.get(pos)
+    }
+
+    fn cell_mut(&mut self, pos: Vec2<isize>) -> Option<&mut T> {
+        self.get_mut(pos)
+    }
+
+    fn set_cell(&mut self, pos: Vec2<isize>, value: T) {
+        self.set(pos, value);
+    }
+
+    fn fingerprint(&self) -> u64 {
+        Grid::fingerprint(self)
+    }
+}
+
+/// A sparse grid is unbounded, but only cells that are set or next to a set
+/// cell are updated. Rules must therefore keep a default cell surrounded by
+/// default cells at the default value.
+impl<T: Clone + PartialEq + Hash> CellSpace for SparseGrid<T> {
+    type Cell = T;
+
+    fn active(&self, neighborhood: Neighborhood) -> Vec<Vec2<isize>> {
+        let mut active = self
+            .iter()
+            .flat_map(|(pos, _)| neighborhood.directions().map(move |dir| pos + dir))
+            .chain(self.iter().map(|(pos, _)| pos))
+            .collect::<Vec<_>>();
+        active.sort_unstable();
+        active.dedup();
+        active
+    }
+
+    fn cell(&self, pos: Vec2<isize>) -> Option<&T> {
+        Some(self.get(pos))
+    }
+
+    fn cell_mut(&mut self, pos: Vec2<isize>) -> Option<&mut T> {
+        Some(self.get_mut(pos))
+    }
+
+    fn set_cell(&mut self, pos: Vec2<isize>, value: T) {
+        if &value == self.default_value() {
+            self.remove(pos);
+        } else {
+            self.set(pos, value);
+        }
+    }
+
+    fn fingerprint(&self) -> u64 {
+        SparseGrid::fingerprint(self)
+    }
+}
+
+/// A repeating sequence of generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation of the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+/// A cellular automaton that evolves a space of cells with a rule. The rule
+/// gets the position of a cell, its value and the values of its neighbors,
+/// and returns the value of the cell in the next generation.
+pub struct Automaton<G: CellSpace, F> {
+    state: G,
+    rule: F,
+    neighborhood: Neighborhood,
+    generation: usize,
+    /// The generation [`Automaton::record`] expects next, for cycle detection.
+    next_record: usize,
+    /// The first recorded generation with each fingerprint.
+    seen: HashMap<u64, usize>,
+    /// A cycle suggested by a repeated fingerprint, together with the state
+    /// at its second occurrence, to be confirmed one cycle length later.
+    candidate: Option<(Cycle, G)>,
+    cycle: Option<Cycle>,
+}
+
+impl<G, F> Automaton<G, F>
+where
+    G: CellSpace,
+    F: FnMut(Vec2<isize>, &G::Cell, &[&G::Cell]) -> G::Cell,
+{
+    pub fn new(state: G, neighborhood: Neighborhood, rule: F) -> Self {
+        Self {
+            state,
+            rule,
+            neighborhood,
+            generation: 0,
+            next_record: 0,
+            seen: HashMap::new(),
+            candidate: None,
+            cycle: None,
+        }
+    }
+
+    pub fn state(&self) -> &G {
+        &self.state
+    }
+
+    pub fn into_state(self) -> G {
+        self.state
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Update all cells at once, returning whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut changes = Vec::new();
+        for pos in self.state.active(self.neighborhood) {
+            let Some(cell) = self.state.cell(pos) else {
+                continue;
+            };
+            let neighbors = self
+                .neighborhood
+                .directions()
+                .filter_map(|dir| self.state.cell(pos + dir))
+                .collect::<Vec<_>>();
+            let next = (self.rule)(pos, cell, &neighbors);
+            if &next != cell {
+                changes.push((pos, next));
+            }
+        }
+
+        let changed = !changes.is_empty();
+        for (pos, value) in changes {
+            self.state.set_cell(pos, value);
+        }
+        self.generation += 1;
+        changed
+    }
+
+    /// Step until no cell changes anymore, returning the number of steps that
+    /// changed a cell.
+    pub fn run_to_fixed_point(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() {}
+        self.generation - start - 1
+    }
+
+    /// Step until a generation repeats an earlier one.
+    pub fn find_cycle(&mut self) -> Cycle {
+        loop {
+            if let Some(cycle) = self.record() {
+                return cycle;
+            }
+            self.step();
+        }
+    }
+
+    /// The state at `generation`, which must not lie before the current one.
+    /// Skips ahead once a cycle is found.
+    pub fn nth(&mut self, generation: usize) -> &G {
+        while self.generation < generation {
+            if let Some(Cycle { length, .. }) = self.record() {
+                // The current generation lies in the cycle already.
+                for _ in 0..(generation - self.generation) % length {
+                    self.step();
+                }
+                self.generation = generation;
+                break;
+            }
+            self.step();
+        }
+        &self.state
+    }
+
+    /// Remember the fingerprint of the current generation, returning the
+    /// cycle once it is confirmed. Only the state at a suspected repeat is
+    /// kept, which must match the state one cycle length later.
+    fn record(&mut self) -> Option<Cycle> {
+        if self.next_record != self.generation {
+            // Generations were skipped, so start over from this one.
+            self.seen.clear();
+            self.candidate = None;
+            self.cycle = None;
+        }
+        self.next_record = self.generation + 1;
+        if self.cycle.is_some() {
+            return self.cycle;
+        }
+
+        if let Some((cycle, state)) = &self.candidate {
+            if self.generation == cycle.start + 2 * cycle.length {
+                if state == &self.state {
+                    self.cycle = Some(*cycle);
+                    return self.cycle;
+                }
+                // The fingerprints collided.
+                self.candidate = None;
+            }
+        }
+
+        match self.seen.entry(self.state.fingerprint()) {
+            Entry::Occupied(entry) => {
+                if self.candidate.is_none() {
+                    let start = *entry.get();
+                    let cycle = Cycle {
+                        start,
+                        length: self.generation - start,
+                    };
+                    self.candidate = Some((cycle, self.state.clone()));
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(self.generation);
+            }
+        }
+        None
+    }
+
+    /// Propagate signals through the cells in place until they settle.
+    /// Every active cell receives one signal first, and `signal` updates a
+    /// cell when it receives one. If `signal` returns `true` the cell fires
+    /// and each of its neighbors receives a signal. Returns the number of
+    /// times a cell fired.
+    pub fn cascade<S>(&mut self, mut signal: S) -> usize
+    where
+        S: FnMut(Vec2<isize>, &mut G::Cell) -> bool,
+    {
+        let mut queue = self.state.active(self.neighborhood);
+        queue.reverse();
+        let mut fired = 0;
+        while let Some(pos) = queue.pop() {
+            let Some(cell) = self.state.cell_mut(pos) else {
+                continue;
+            };
+            if signal(pos, cell) {
+                fired += 1;
+                queue.extend(self.neighborhood.directions().map(|dir| pos + dir));
+            }
+        }
+        self.generation += 1;
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(_: Vec2<isize>, &alive: &bool, neighbors: &[&bool]) -> bool {
+        let n = neighbors.iter().filter(|&&&v| v).count();
+        n == 3 || alive && n == 2
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut grid = Grid::new(5, 5);
+        for x in 1..4 {
+            grid[Vec2::new(x, 2)] = true;
+        }
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, life);
+        assert_eq!(
+            automaton.find_cycle(),
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        assert!(automaton.nth(1001)[Vec2::new(2, 1)]);
+        assert!(automaton.nth(1001)[Vec2::new(2, 3)]);
+        assert_eq!(automaton.generation(), 1001);
+    }
+
+    #[test]
+    fn test_sparse_glider() {
+        let mut grid = SparseGrid::new(false);
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            grid.set(Vec2::new(x, y), true);
+        }
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, life);
+        for _ in 0..8 {
+            automaton.step();
+        }
+        let mut cells = automaton
+            .state()
+            .iter()
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells.len(), 5);
+        assert!(cells.contains(&Vec2::new(4, 4)));
+    }
+
+    #[test]
+    fn test_fixed_point_and_cascade() {
+        // Erosion removes one layer of a 5x5 square per step.
+        let grid = Grid::new_default(5, 5, true);
+        let mut automaton = Automaton::new(grid, Neighborhood::VonNeumann, |_, &v, n| {
+            v && n.len() == 4 && n.iter().all(|&&v| v)
+        });
+        assert_eq!(automaton.run_to_fixed_point(), 3);
+        assert_eq!(automaton.generation(), 4);
+
+        // A single charged cell sets off all others.
+        let mut grid = Grid::new_default(3, 3, 8);
+        grid[Vec2::new(1, 1)] = 9;
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, |_, &v, _| v);
+        let fired = automaton.cascade(|_, v| {
+            *v += 1;
+            *v == 10
+        });
+        assert_eq!(fired, 9);
+        assert_eq!(automaton.state()[Vec2::new(0, 0)], 12);
+
+        // Signalling empty cells of a sparse grid stores them explicitly,
+        // which must not change its logical state.
+        let mut grid = SparseGrid::new(0);
+        grid.set(Vec2::new(0, 0), 9);
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, |_, &v, _| v);
+        let fired = automaton.cascade(|_, v| {
+            let fire = *v == 9;
+            if fire {
+                *v = 0;
+            }
+            fire
+        });
+        assert_eq!(fired, 1);
+        assert!(automaton.state().len() > 1);
+        let empty = SparseGrid::new(0);
+        assert!(automaton.state() == &empty);
+        assert_eq!(automaton.state().fingerprint(), empty.fingerprint());
+    }
+}
@@ -0,0 +1,234 @@
+use num_integer::Integer;
+
+use super::VecN;
+
+/// An axis-aligned box of integer points in `N` dimensions. The box is
+/// half-open: it contains `min` but not `max`. Boxes given by inclusive
+/// corners are built with [`AxisBox::inclusive`] and read back with
+/// [`AxisBox::inclusive_bounds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AxisBox<T, const N: usize> {
+    min: VecN<T, N>,
+    max: VecN<T, N>,
+}
+
+pub type Rect<T> = AxisBox<T, 2>;
+pub type Cuboid<T> = AxisBox<T, 3>;
+
+impl<T: Integer + Copy, const N: usize> AxisBox<T, N> {
+    /// The box from `min` up to but not including `max`.
+    pub fn new(min: VecN<T, N>, max: VecN<T, N>) -> Self {
+        Self { min, max }
+    }
+
+    /// The box from `min` up to and including `max`.
+    pub fn inclusive(min: VecN<T, N>, max: VecN<T, N>) -> Self {
+        Self::new(min, max + T::one())
+    }
+
+    /// The smallest box containing both corners, in any order.
+    pub fn from_corners(a: VecN<T, N>, b: VecN<T, N>) -> Self {
+        Self::inclusive(a.zip_map(b, T::min), a.zip_map(b, T::max))
+    }
+
+    /// The smallest box containing all `points`, or `None` if there are none.
+    pub fn bounding<I: IntoIterator<Item = VecN<T, N>>>(points: I) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|p| Self::inclusive(p, p))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    pub fn min(&self) -> VecN<T, N> {
+        self.min
+    }
+
+    /// The exclusive upper corner.
+    pub fn max(&self) -> VecN<T, N> {
+        self.max
+    }
+
+    /// The inclusive upper corner. Meaningless for an empty box.
+    pub fn max_inclusive(&self) -> VecN<T, N> {
+        self.max - T::one()
+    }
+
+    /// The inclusive minimum and maximum corners, as passed to
+    /// [`AxisBox::inclusive`], or `None` if the box is empty.
+    pub fn inclusive_bounds(&self) -> Option<(VecN<T, N>, VecN<T, N>)> {
+        (!self.is_empty()).then(|| (self.min, self.max_inclusive()))
+    }
+
+    /// The number of points along each axis.
+    pub fn size(&self) -> VecN<T, N> {
+        self.max.zip_map(self.min, |max, min| max.max(min) - min)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.zip_map(self.max, |min, max| min >= max).any()
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> T {
+        self.size().0.into_iter().fold(T::one(), |v, a| v * a)
+    }
+
+    pub fn contains(&self, pos: VecN<T, N>) -> bool {
+        (0..N).all(|i| self.min[i] <= pos[i] && pos[i] < self.max[i])
+    }
+
+    /// Whether every point of `other` lies in this box.
+    pub fn contains_box(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (0..N).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The points in both boxes, or `None` if there are none.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(
+            self.min.zip_map(other.min, T::max),
+            self.max.zip_map(other.max, T::min),
+        );
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+        Self::new(
+            self.min.zip_map(other.min, T::min),
+            self.max.zip_map(other.max, T::max),
+        )
+    }
+
+    /// Split the points of this box that are not in `other` into at most
+    /// `2 * N` disjoint boxes.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersect(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+        // Slice off the parts below and above the cut along each axis in turn,
+        // narrowing the remainder down to the cut.
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for i in 0..N {
+            if rest.min[i] < cut.min[i] {
+                let mut below = rest;
+                below.max[i] = cut.min[i];
+                pieces.push(below);
+            }
+            if cut.max[i] < rest.max[i] {
+                let mut above = rest;
+                above.min[i] = cut.max[i];
+                pieces.push(above);
+            }
+            rest.min[i] = cut.min[i];
+            rest.max[i] = cut.max[i];
+        }
+        pieces
+    }
+
+    /// All points of the box, with the first axis changing fastest. For a
+    /// [`Rect`] these are row by row.
+    pub fn points(&self) -> impl Iterator<Item = VecN<T, N>> {
+        let (min, max) = (self.min, self.max);
+        let start = (!self.is_empty()).then_some(min);
+        std::iter::successors(start, move |&pos| {
+            let mut pos = pos;
+            for i in 0..N {
+                pos[i] = pos[i] + T::one();
+                if pos[i] < max[i] {
+                    return Some(pos);
+                }
+                pos[i] = min[i];
+            }
+            None
+        })
+    }
+}
+
+impl<T: Integer + Copy> Rect<T> {
+    pub fn area(&self) -> T {
+        self.volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Vec2, Vec3};
+
+    #[test]
+    fn test_rect() {
+        let a = Rect::from_corners(Vec2::new(3, 0), Vec2::new(0, 2));
+        assert_eq!(a.area(), 12);
+        assert!(a.contains(Vec2::new(3, 2)));
+        assert!(!a.contains(Vec2::new(4, 2)));
+        let b = Rect::new(Vec2::new(2, 1), Vec2::new(6, 2));
+        assert_eq!(
+            a.intersect(&b),
+            Some(Rect::new(Vec2::new(2, 1), Vec2::new(4, 2)))
+        );
+        assert_eq!(a.union(&b), Rect::new(Vec2::new(0, 0), Vec2::new(6, 3)));
+        assert!(!a.overlaps(&Rect::new(Vec2::new(4, 0), Vec2::new(5, 3))));
+        assert_eq!(
+            b.points().collect::<Vec<_>>(),
+            (2..6).map(|x| Vec2::new(x, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Rect::bounding([Vec2::new(1, 5), Vec2::new(-2, 3)]),
+            Some(Rect::inclusive(Vec2::new(-2, 3), Vec2::new(1, 5)))
+        );
+    }
+
+    #[test]
+    fn test_inclusive() {
+        let half_open = Rect::new(Vec2::new(-1, 2), Vec2::new(3, 4));
+        let inclusive = Rect::inclusive(Vec2::new(-1, 2), Vec2::new(2, 3));
+        assert_eq!(half_open, inclusive);
+        assert_eq!(inclusive.max(), Vec2::new(3, 4));
+        assert_eq!(inclusive.max_inclusive(), Vec2::new(2, 3));
+        assert_eq!(
+            inclusive.inclusive_bounds(),
+            Some((Vec2::new(-1, 2), Vec2::new(2, 3)))
+        );
+        assert!(inclusive.contains(Vec2::new(2, 3)));
+        assert!(!half_open.contains(Vec2::new(3, 3)));
+
+        let point = Cuboid::inclusive(Vec3::new(1, 2, 3), Vec3::new(1, 2, 3));
+        assert_eq!(point.volume(), 1);
+        assert_eq!(
+            point.inclusive_bounds().map(|(min, _)| min),
+            Some(point.min())
+        );
+        let empty = Cuboid::new(Vec3::new(1, 2, 3), Vec3::new(1, 5, 5));
+        assert!(empty.is_empty());
+        assert_eq!(empty.inclusive_bounds(), None);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = Cuboid::new(Vec3::new(0, 0, 0), Vec3::new(4, 4, 4));
+        let b = Cuboid::new(Vec3::new(1, 1, 1), Vec3::new(2, 5, 3));
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 5);
+        assert_eq!(
+            pieces.iter().map(Cuboid::volume).sum::<i32>(),
+            a.volume() - a.intersect(&b).unwrap().volume()
+        );
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(a.contains_box(p) && !p.overlaps(&b));
+            assert!(pieces[i + 1..].iter().all(|q| !p.overlaps(q)));
+        }
+        assert!(b.subtract(&a).iter().all(|p| p.min().y == 4));
+        assert_eq!(a.subtract(&a), []);
+    }
+}
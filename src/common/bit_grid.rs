@@ -0,0 +1,380 @@
+use std::fmt::Display;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::{Grid, Vec2};
+
+/// A grid of booleans, packing every row into `u64` words. Bit `x % 64` of
+/// word `x / 64` holds column `x`; bits past the width are always unset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    words: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words,
+            data: vec![0; words * height],
+        }
+    }
+
+    pub fn contains<C: Into<Vec2<isize>>>(&self, pos: C) -> bool {
+        let pos = pos.into();
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.width as isize && pos.y < self.height as isize
+    }
+
+    /// The value at `pos`, or `false` if it lies outside the grid.
+    pub fn get<C: Into<Vec2<isize>>>(&self, pos: C) -> bool {
+        let pos = pos.into();
+        if !self.contains(pos) {
+            return false;
+        }
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        self.data[y * self.words + x / 64] >> (x % 64) & 1 == 1
+    }
+
+    pub fn set<C: Into<Vec2<isize>>>(&mut self, pos: C, value: bool) {
+        let pos = pos.into();
+        if !self.contains(pos) {
+            return;
+        }
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        let word = &mut self.data[y * self.words + x / 64];
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    pub fn toggle<C: Into<Vec2<isize>>>(&mut self, pos: C) {
+        let pos = pos.into();
+        self.set(pos, !self.get(pos));
+    }
+
+    /// Set every cell in the inclusive rectangle from `min` to `max`.
+    pub fn fill_rect(&mut self, min: Vec2<isize>, max: Vec2<isize>, value: bool) {
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.set(Vec2::new(x, y), value);
+            }
+        }
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Positions of all set cells, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width)
+                .map(move |x| Vec2::new(x as isize, y as isize))
+                .filter(|&pos| self.get(pos))
+        })
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words..(y + 1) * self.words]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.data[y * self.words..(y + 1) * self.words]
+    }
+
+    /// Mask of the valid bits of the last word of a row.
+    fn last_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => !0,
+            r => (1 << r) - 1,
+        }
+    }
+
+    /// Shift the words of a row by `n` columns, towards higher columns if `n`
+    /// is positive. Bits shifted in are unset.
+    fn shifted(&self, row: &[u64], n: isize) -> Vec<u64> {
+        let (q, r) = (n.unsigned_abs() / 64, n.unsigned_abs() % 64);
+        let word = |i: isize| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| row.get(i))
+                .copied()
+                .unwrap_or(0)
+        };
+        let mut shifted = (0..self.words as isize)
+            .map(|i| {
+                if n >= 0 {
+                    let i = i - q as isize;
+                    let carry = if r > 0 { word(i - 1) >> (64 - r) } else { 0 };
+                    word(i) << r | carry
+                } else {
+                    let i = i + q as isize;
+                    let carry = if r > 0 { word(i + 1) << (64 - r) } else { 0 };
+                    word(i) >> r | carry
+                }
+            })
+            .collect::<Vec<_>>();
+        if let Some(last) = shifted.last_mut() {
+            *last &= self.last_mask();
+        }
+        shifted
+    }
+
+    /// Shift row `y` by `n` columns to the right (left if negative), unsetting
+    /// the cells that are shifted in.
+    pub fn shift_row(&mut self, y: usize, n: isize) {
+        let shifted = self.shifted(self.row(y), n);
+        self.row_mut(y).copy_from_slice(&shifted);
+    }
+
+    /// Rotate row `y` by `n` columns to the right (left if negative).
+    pub fn rotate_row(&mut self, y: usize, n: isize) {
+        if self.width == 0 {
+            return;
+        }
+        let n = n.rem_euclid(self.width as isize);
+        let right = self.shifted(self.row(y), n);
+        let left = self.shifted(self.row(y), n - self.width as isize);
+        for (word, (r, l)) in self.row_mut(y).iter_mut().zip(right.into_iter().zip(left)) {
+            *word = r | l;
+        }
+    }
+
+    /// Shift column `x` by `n` rows down (up if negative), unsetting the cells
+    /// that are shifted in.
+    pub fn shift_column(&mut self, x: usize, n: isize) {
+        let column = self.column(x);
+        for y in 0..self.height {
+            let from = y as isize - n;
+            let value = from >= 0 && (from as usize) < self.height && column[from as usize];
+            self.set(Vec2::new(x as isize, y as isize), value);
+        }
+    }
+
+    /// Rotate column `x` by `n` rows down (up if negative).
+    pub fn rotate_column(&mut self, x: usize, n: isize) {
+        let column = self.column(x);
+        for y in 0..self.height {
+            let from = (y as isize - n).rem_euclid(self.height as isize) as usize;
+            self.set(Vec2::new(x as isize, y as isize), column[from]);
+        }
+    }
+
+    fn column(&self, x: usize) -> Vec<bool> {
+        (0..self.height)
+            .map(|y| self.get(Vec2::new(x as isize, y as isize)))
+            .collect()
+    }
+
+    /// Advance one generation of Conway's Game of Life, where cells outside
+    /// the grid are dead.
+    pub fn life_step(&self) -> Self {
+        self.life_step_with(&[3], &[2, 3])
+    }
+
+    /// Advance one generation of a life-like automaton, where a dead cell
+    /// becomes alive if its number of live neighbors is in `birth` and a live
+    /// cell stays alive if it is in `survive`. All cells of a word are updated
+    /// at once by counting neighbors with bit-sliced adders.
+    pub fn life_step_with(&self, birth: &[u8], survive: &[u8]) -> Self {
+        let empty = vec![0; self.words];
+        let mut next = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            let mut neighbors = Vec::with_capacity(8);
+            for dy in [-1, 0, 1] {
+                let row = match y.checked_add_signed(dy) {
+                    Some(y) if y < self.height => self.row(y),
+                    _ => &empty,
+                };
+                neighbors.push(self.shifted(row, 1));
+                neighbors.push(self.shifted(row, -1));
+                if dy != 0 {
+                    neighbors.push(row.to_vec());
+                }
+            }
+
+            let alive = self.row(y);
+            for (i, word) in next.row_mut(y).iter_mut().enumerate() {
+                // Four bit planes are enough to count up to eight neighbors.
+                let mut count = [0u64; 4];
+                for plane in &neighbors {
+                    let mut carry = plane[i];
+                    for bit in count.iter_mut() {
+                        let sum = *bit ^ carry;
+                        carry &= *bit;
+                        *bit = sum;
+                    }
+                }
+                let equals = |n: u8| {
+                    count.iter().enumerate().fold(!0, |acc, (b, &bit)| {
+                        acc & if n >> b & 1 == 1 { bit } else { !bit }
+                    })
+                };
+                let born = birth.iter().fold(0, |acc, &n| acc | equals(n));
+                let stays = survive.iter().fold(0, |acc, &n| acc | equals(n));
+                *word = (alive[i] & stays) | (!alive[i] & born);
+            }
+            if let Some(last) = next.row_mut(y).last_mut() {
+                *last &= self.last_mask();
+            }
+        }
+        next
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_data(
+            self.width,
+            self.height,
+            (0..self.height)
+                .flat_map(|y| (0..self.width).map(move |x| Vec2::new(x as isize, y as isize)))
+                .map(|pos| self.get(pos))
+                .collect(),
+        )
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = Self::new(grid.width, grid.height);
+        for pos in grid.coordinates().filter(|&pos| grid[pos]) {
+            bits.set(pos, true);
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        bits.to_grid()
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let on = self.get(Vec2::new(x as isize, y as isize));
+                write!(f, "{}", if on { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl $op_assign<&BitGrid> for BitGrid {
+            fn $fn_assign(&mut self, rhs: &BitGrid) {
+                assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+                for (a, &b) in self.data.iter_mut().zip(&rhs.data) {
+                    a.$fn_assign(b);
+                }
+            }
+        }
+
+        impl $op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $fn(self, rhs: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$fn_assign(rhs);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        let mask = self.last_mask();
+        for row in result.data.chunks_mut(self.words.max(1)) {
+            for word in row.iter_mut() {
+                *word = !*word;
+            }
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(rows: &[&str]) -> BitGrid {
+        let mut bits = BitGrid::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                bits.set(Vec2::new(x as isize, y as isize), c == '#');
+            }
+        }
+        bits
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut grid = BitGrid::new(70, 3);
+        grid.set(Vec2::new(62, 0), true);
+        grid.rotate_row(0, 5);
+        assert!(grid.get(Vec2::new(67, 0)));
+        grid.rotate_row(0, 5);
+        assert!(grid.get(Vec2::new(2, 0)));
+        grid.rotate_row(0, -3);
+        assert!(grid.get(Vec2::new(69, 0)));
+        grid.rotate_column(69, 4);
+        assert!(grid.get(Vec2::new(69, 1)));
+        grid.shift_row(1, 1);
+        assert_eq!(grid.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_boolean_ops() {
+        let a = bits(&["##..", "#.#."]);
+        let b = bits(&[".#.#", "#..."]);
+        assert_eq!(&a & &b, bits(&[".#..", "#..."]));
+        assert_eq!(&a | &b, bits(&["##.#", "#.#."]));
+        assert_eq!(&a ^ &b, bits(&["#..#", "..#."]));
+        assert_eq!(!&a, bits(&["..##", ".#.#"]));
+        assert_eq!((!&a).count_ones(), 4);
+    }
+
+    #[test]
+    fn test_life_step() {
+        // A glider crossing the boundary between two words.
+        let mut grid = BitGrid::new(100, 6);
+        for (x, y) in [(63, 0), (64, 1), (62, 2), (63, 2), (64, 2)] {
+            grid.set(Vec2::new(x, y), true);
+        }
+        let mut slow = grid.to_grid();
+        for _ in 0..4 {
+            grid = grid.life_step();
+            slow = slow.map(|pos, &alive| {
+                let n = slow.all_neighbor_values(pos).filter(|(_, &v)| v).count();
+                n == 3 || alive && n == 2
+            });
+            assert_eq!(grid, BitGrid::from(&slow));
+        }
+        assert!(grid.get(Vec2::new(65, 3)));
+    }
+}
@@ -5,16 +5,19 @@ use crate::common::Vec2;
 
 /// A cardinal direction.
 /// North, East, South, West.
+///
+/// The discriminants are the bit positions in a
+/// [`DirectionSet`](super::DirectionSet), so they must not change.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-    NorthEast,
-    NorthWest,
-    SouthEast,
-    SouthWest,
+    North = 0,
+    East = 1,
+    South = 2,
+    West = 3,
+    NorthEast = 4,
+    NorthWest = 5,
+    SouthEast = 6,
+    SouthWest = 7,
 }
 
 impl Direction {
@@ -0,0 +1,280 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Index, IndexMut, Not, Sub, SubAssign};
+
+use super::Direction;
+
+/// The directions in the order of their bits in a [`DirectionSet`].
+const DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+    Direction::NorthEast,
+    Direction::NorthWest,
+    Direction::SouthEast,
+    Direction::SouthWest,
+];
+
+// Sets and maps index by `dir as u8`, which must match the table order.
+const _: () = {
+    let mut i = 0;
+    while i < DIRECTIONS.len() {
+        assert!(DIRECTIONS[i] as usize == i);
+        i += 1;
+    }
+};
+
+/// Pipe glyphs, as in 2023 day 10, and the sides they connect.
+const PIPE_GLYPHS: [(char, u8); 8] = [
+    ('.', 0b0000),
+    ('|', 0b0101),
+    ('-', 0b1010),
+    ('L', 0b0011),
+    ('J', 0b1001),
+    ('7', 0b1100),
+    ('F', 0b0110),
+    ('+', 0b1111),
+];
+
+/// Light box-drawing glyphs, indexed by the bits of the sides they connect.
+const BOX_GLYPHS: [char; 16] = [
+    ' ', '╵', '╶', '└', '╷', '│', '┌', '├', '╴', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+];
+
+/// A set of directions, stored as one bit per direction.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const EMPTY: Self = Self(0);
+    pub const CARDINAL: Self = Self(0b0000_1111);
+    pub const ORDINAL: Self = Self(0b1111_0000);
+    pub const ALL: Self = Self(0b1111_1111);
+
+    const fn bit(dir: Direction) -> u8 {
+        1 << dir as u8
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(self, dir: Direction) -> bool {
+        self.0 & Self::bit(dir) != 0
+    }
+
+    /// Add `dir`, returning whether it was not in the set yet.
+    pub fn insert(&mut self, dir: Direction) -> bool {
+        let inserted = !self.contains(dir);
+        self.0 |= Self::bit(dir);
+        inserted
+    }
+
+    /// Remove `dir`, returning whether it was in the set.
+    pub fn remove(&mut self, dir: Direction) -> bool {
+        let removed = self.contains(dir);
+        self.0 &= !Self::bit(dir);
+        removed
+    }
+
+    pub fn toggle(&mut self, dir: Direction) {
+        self.0 ^= Self::bit(dir);
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        DIRECTIONS
+            .into_iter()
+            .filter(move |&dir| self.contains(dir))
+    }
+
+    /// Turn every direction in the set to the right.
+    pub fn right(self) -> Self {
+        self.iter().map(Direction::right).collect()
+    }
+
+    /// Turn every direction in the set to the left.
+    pub fn left(self) -> Self {
+        self.iter().map(Direction::left).collect()
+    }
+
+    /// Turn every direction in the set around.
+    pub fn opposite(self) -> Self {
+        self.iter().map(Direction::opposite).collect()
+    }
+
+    /// The sides connected by a pipe (`|-LJ7F.+`) or light box-drawing
+    /// glyph.
+    pub fn from_glyph(c: char) -> Option<Self> {
+        PIPE_GLYPHS
+            .iter()
+            .find(|&&(glyph, _)| glyph == c)
+            .map(|&(_, bits)| Self(bits))
+            .or_else(|| {
+                let bits = BOX_GLYPHS.iter().position(|&glyph| glyph == c)?;
+                Some(Self(bits as u8))
+            })
+    }
+
+    /// The light box-drawing glyph connecting the directions in the set, or
+    /// `None` if it contains an ordinal direction.
+    pub fn to_glyph(self) -> Option<char> {
+        (self & Self::ORDINAL)
+            .is_empty()
+            .then(|| BOX_GLYPHS[self.0 as usize])
+    }
+}
+
+impl From<Direction> for DirectionSet {
+    fn from(dir: Direction) -> Self {
+        Self(Self::bit(dir))
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        Self(iter.into_iter().fold(0, |bits, dir| bits | Self::bit(dir)))
+    }
+}
+
+impl std::fmt::Debug for DirectionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for DirectionSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for DirectionSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for DirectionSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl Sub for DirectionSet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl SubAssign for DirectionSet {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl Not for DirectionSet {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0)
+    }
+}
+
+/// A value for each direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DirectionMap<T>([T; 8]);
+
+impl<T> DirectionMap<T> {
+    pub fn new(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self(std::array::from_fn(|_| value.clone()))
+    }
+
+    pub fn from_fn<F: FnMut(Direction) -> T>(mut f: F) -> Self {
+        Self(DIRECTIONS.map(&mut f))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        DIRECTIONS.into_iter().zip(self.0.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Direction, &mut T)> {
+        DIRECTIONS.into_iter().zip(self.0.iter_mut())
+    }
+}
+
+impl<T> Index<Direction> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, dir: Direction) -> &Self::Output {
+        &self.0[dir as usize]
+    }
+}
+
+impl<T> IndexMut<Direction> for DirectionMap<T> {
+    fn index_mut(&mut self, dir: Direction) -> &mut Self::Output {
+        &mut self.0[dir as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        use Direction::*;
+        let mut set = DirectionSet::from_glyph('L').unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [North, East]);
+        assert!(!set.insert(North));
+        assert!(set.insert(SouthWest));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_glyph(), None);
+        assert!(set.remove(SouthWest));
+        assert_eq!(set.right(), DirectionSet::from_glyph('F').unwrap());
+        assert_eq!(set.left().to_glyph(), Some('┘'));
+        assert_eq!(set.opposite(), [South, West].into_iter().collect());
+        assert_eq!(
+            DirectionSet::from_glyph('├'),
+            Some(set | DirectionSet::from(South))
+        );
+        assert_eq!(!set & DirectionSet::CARDINAL, set.opposite());
+        assert_eq!(DirectionSet::CARDINAL - set, set.opposite());
+        assert_eq!(DirectionSet::from_glyph('x'), None);
+        assert_eq!(format!("{:?}", set), "{North, East}");
+    }
+
+    #[test]
+    fn test_map() {
+        let mut map = DirectionMap::from_fn(|dir| dir.is_cardinal());
+        assert!(map[Direction::West] && !map[Direction::NorthWest]);
+        map[Direction::West] = false;
+        assert_eq!(map.iter().filter(|(_, &v)| v).count(), 3);
+        assert_eq!(DirectionMap::new(0u8), DirectionMap::default());
+    }
+}
@@ -0,0 +1,482 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num_integer::Integer;
+
+use super::{Vec2, Vec3};
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    pub const ZERO: Ratio = Ratio { num: 0, den: 1 };
+    pub const ONE: Ratio = Ratio { num: 1, den: 1 };
+
+    /// The fraction `num / den`. Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "denominator is zero");
+        let g = num.gcd(&den) * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// The value if it is a whole number.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn floor(&self) -> i128 {
+        Integer::div_floor(&self.num, &self.den)
+    }
+
+    pub fn ceil(&self) -> i128 {
+        -Integer::div_floor(&-self.num, &self.den)
+    }
+
+    /// An approximation for display purposes only.
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+/// `a * b`, panicking instead of wrapping on overflow.
+fn mul_exact(a: i128, b: i128) -> i128 {
+    a.checked_mul(b)
+        .unwrap_or_else(|| panic!("Ratio overflow computing {a} * {b}"))
+}
+
+/// `a + b`, panicking instead of wrapping on overflow.
+fn add_exact(a: i128, b: i128) -> i128 {
+    a.checked_add(b)
+        .unwrap_or_else(|| panic!("Ratio overflow computing {a} + {b}"))
+}
+
+/// `a - b`, panicking instead of wrapping on overflow.
+fn sub_exact(a: i128, b: i128) -> i128 {
+    a.checked_sub(b)
+        .unwrap_or_else(|| panic!("Ratio overflow computing {a} - {b}"))
+}
+
+impl From<i64> for Ratio {
+    fn from(n: i64) -> Self {
+        Self {
+            num: n as i128,
+            den: 1,
+        }
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Scale both to the least common denominator, which is positive.
+        let g = self.den.gcd(&other.den);
+        mul_exact(self.num, other.den / g).cmp(&mul_exact(other.num, self.den / g))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Ratio {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let g = self.den.gcd(&rhs.den);
+        Self::new(
+            add_exact(
+                mul_exact(self.num, rhs.den / g),
+                mul_exact(rhs.num, self.den / g),
+            ),
+            mul_exact(self.den / g, rhs.den),
+        )
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Cancel across the pair first, so the products are in lowest terms.
+        let g1 = self.num.gcd(&rhs.den);
+        let g2 = rhs.num.gcd(&self.den);
+        Self::new(
+            mul_exact(self.num / g1, rhs.num / g2),
+            mul_exact(self.den / g2, rhs.den / g1),
+        )
+    }
+}
+
+impl Div for Ratio {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(rhs.num != 0, "division by zero");
+        let g1 = self.num.gcd(&rhs.num);
+        let g2 = rhs.den.gcd(&self.den);
+        Self::new(
+            mul_exact(self.num / g1, rhs.den / g2),
+            mul_exact(self.den / g2, rhs.num / g1),
+        )
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+fn widen2(v: Vec2<i64>) -> Vec2<i128> {
+    v.map(|a| a as i128)
+}
+
+fn widen3(v: Vec3<i64>) -> Vec3<i128> {
+    v.map(|a| a as i128)
+}
+
+fn dot_exact(a: Vec3<i128>, b: Vec3<i128>) -> i128 {
+    (0..3).fold(0, |sum, i| add_exact(sum, mul_exact(a[i], b[i])))
+}
+
+/// The points `point + t * dir` for all `t`. With a velocity as `dir`, `t`
+/// is the time at which the point is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub point: Vec2<i64>,
+    pub dir: Vec2<i64>,
+}
+
+/// How two lines meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineIntersection {
+    Parallel,
+    Coincident,
+    /// The lines cross at `pos`, which lies at parameter `t` of the first
+    /// line and `u` of the second.
+    Point {
+        pos: Vec2<Ratio>,
+        t: Ratio,
+        u: Ratio,
+    },
+}
+
+impl Line {
+    pub fn new(point: Vec2<i64>, dir: Vec2<i64>) -> Self {
+        Self { point, dir }
+    }
+
+    /// The line through `a` and `b`, at `t = 0` and `t = 1` respectively.
+    pub fn through(a: Vec2<i64>, b: Vec2<i64>) -> Self {
+        Self::new(a, b - a)
+    }
+
+    pub fn at(&self, t: Ratio) -> Vec2<Ratio> {
+        widen2(self.point).map(Ratio::from) + widen2(self.dir).map(|a| Ratio::from(a) * t)
+    }
+
+    pub fn intersect(&self, other: &Line) -> LineIntersection {
+        let (p, r) = (widen2(self.point), widen2(self.dir));
+        let (q, s) = (widen2(other.point), widen2(other.dir));
+        let denom = r.cross(s);
+        if denom == 0 {
+            return if (q - p).cross(r) == 0 {
+                LineIntersection::Coincident
+            } else {
+                LineIntersection::Parallel
+            };
+        }
+        let t = Ratio::new((q - p).cross(s), denom);
+        let u = Ratio::new((q - p).cross(r), denom);
+        LineIntersection::Point {
+            pos: self.at(t),
+            t,
+            u,
+        }
+    }
+
+    /// Where the paths of two points moving from `point` with velocity `dir`
+    /// cross, if both reach it at a time that is not negative. Returns the
+    /// position and the times at which each of them passes it.
+    pub fn ray_intersection(&self, other: &Line) -> Option<(Vec2<Ratio>, Ratio, Ratio)> {
+        match self.intersect(other) {
+            LineIntersection::Point { pos, t, u } if t >= Ratio::ZERO && u >= Ratio::ZERO => {
+                Some((pos, t, u))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The points on the straight line from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Vec2<i64>,
+    pub end: Vec2<i64>,
+}
+
+/// The points two segments have in common.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentIntersection {
+    None,
+    Point(Vec2<Ratio>),
+    /// Collinear segments share the segment between these points.
+    Overlap(Vec2<i64>, Vec2<i64>),
+}
+
+impl Segment {
+    pub fn new(start: Vec2<i64>, end: Vec2<i64>) -> Self {
+        Self { start, end }
+    }
+
+    pub fn intersect(&self, other: &Segment) -> SegmentIntersection {
+        let line = Line::through(self.start, self.end);
+        match line.intersect(&Line::through(other.start, other.end)) {
+            LineIntersection::Parallel => SegmentIntersection::None,
+            LineIntersection::Point { pos, t, u } => {
+                let on = |t| Ratio::ZERO <= t && t <= Ratio::ONE;
+                if on(t) && on(u) {
+                    SegmentIntersection::Point(pos)
+                } else {
+                    SegmentIntersection::None
+                }
+            }
+            LineIntersection::Coincident => self.overlap(other),
+        }
+    }
+
+    /// The overlap of two segments whose lines coincide. A segment that is
+    /// a single point has no direction and coincides with every line, so
+    /// check those separately.
+    fn overlap(&self, other: &Segment) -> SegmentIntersection {
+        let (a, b) = if self.start != self.end {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let dir = widen2(a.end - a.start);
+        let on_line = |p: Vec2<i64>| widen2(p - a.start).cross(dir) == 0;
+        if dir == Vec2::new(0, 0) && a.start != b.start || !on_line(b.start) || !on_line(b.end) {
+            return SegmentIntersection::None;
+        }
+
+        let key = |p: Vec2<i64>| widen2(p).dot(dir);
+        let ordered = |s: &Segment| {
+            if key(s.start) <= key(s.end) {
+                (s.start, s.end)
+            } else {
+                (s.end, s.start)
+            }
+        };
+        let ((a0, a1), (b0, b1)) = (ordered(a), ordered(b));
+        let lo = if key(a0) >= key(b0) { a0 } else { b0 };
+        let hi = if key(a1) <= key(b1) { a1 } else { b1 };
+        match key(lo).cmp(&key(hi)) {
+            Ordering::Less => SegmentIntersection::Overlap(lo, hi),
+            Ordering::Equal => SegmentIntersection::Point(widen2(lo).map(Ratio::from)),
+            Ordering::Greater => SegmentIntersection::None,
+        }
+    }
+}
+
+/// The points `point + t * dir` in space for all `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line3 {
+    pub point: Vec3<i64>,
+    pub dir: Vec3<i64>,
+}
+
+impl Line3 {
+    pub fn new(point: Vec3<i64>, dir: Vec3<i64>) -> Self {
+        Self { point, dir }
+    }
+
+    pub fn at(&self, t: Ratio) -> Vec3<Ratio> {
+        widen3(self.point).map(Ratio::from) + widen3(self.dir).map(|a| Ratio::from(a) * t)
+    }
+
+    /// The parameters `t` and `u` of the points where this line and `other`
+    /// come closest, or `None` if they are parallel.
+    pub fn closest_approach(&self, other: &Line3) -> Option<(Ratio, Ratio)> {
+        let (d, e) = (widen3(self.dir), widen3(other.dir));
+        let w = widen3(self.point) - widen3(other.point);
+        let (a, b, c) = (dot_exact(d, d), dot_exact(d, e), dot_exact(e, e));
+        let (dw, ew) = (dot_exact(d, w), dot_exact(e, w));
+        let det = |x, y, z, w| sub_exact(mul_exact(x, y), mul_exact(z, w));
+        let denom = det(a, c, b, b);
+        if denom == 0 {
+            return None;
+        }
+        Some((
+            Ratio::new(det(b, ew, c, dw), denom),
+            Ratio::new(det(a, ew, b, dw), denom),
+        ))
+    }
+
+    /// The point where the lines meet, if they do.
+    pub fn intersect(&self, other: &Line3) -> Option<Vec3<Ratio>> {
+        let (t, u) = self.closest_approach(other)?;
+        let pos = self.at(t);
+        (pos == other.at(u)).then_some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        let a = Ratio::new(6, -4);
+        assert_eq!((a.numer(), a.denom()), (-3, 2));
+        assert_eq!((a.floor(), a.ceil()), (-2, -1));
+        assert_eq!(a + Ratio::new(1, 2), Ratio::from(-1i64));
+        assert_eq!((a * a / Ratio::new(9, 2)).to_string(), "1/2");
+        assert!(a < Ratio::new(-4, 3));
+
+        // Intermediate products that would not fit in an i128.
+        let (big, tiny) = (Ratio::new(1 << 100, 3), Ratio::new(1, 1 << 100));
+        assert_eq!(tiny + tiny, Ratio::new(1, 1 << 99));
+        assert_eq!(tiny - Ratio::new(3, 1 << 101), Ratio::new(-1, 1 << 101));
+        assert_eq!(big * tiny, Ratio::new(1, 3));
+        assert_eq!(big / big, Ratio::ONE);
+        assert!(tiny < Ratio::new(3, 1 << 101));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ratio overflow")]
+    fn test_ratio_overflow() {
+        let big = Ratio::from(1i128 << 100);
+        let _ = big * big;
+    }
+
+    #[test]
+    fn test_hailstone_paths() {
+        let stones = [
+            ((19, 13), (-2, 1)),
+            ((18, 19), (-1, -1)),
+            ((20, 25), (-2, -2)),
+            ((12, 31), (-1, -2)),
+            ((20, 19), (1, -5)),
+        ]
+        .map(|(p, v)| Line::new(p.into(), v.into()));
+        let area = Ratio::from(7i64)..=Ratio::from(27i64);
+        let crossings = (0..5)
+            .flat_map(|i| (i + 1..5).map(move |j| (i, j)))
+            .filter_map(|(i, j)| stones[i].ray_intersection(&stones[j]))
+            .filter(|(pos, _, _)| area.contains(&pos.x) && area.contains(&pos.y))
+            .collect::<Vec<_>>();
+        assert_eq!(crossings.len(), 2);
+        assert_eq!(
+            crossings[0].0,
+            Vec2::new(Ratio::new(43, 3), Ratio::new(46, 3))
+        );
+        assert_eq!(crossings[0].1, Ratio::new(7, 3));
+        assert_eq!(stones[1].intersect(&stones[2]), LineIntersection::Parallel);
+    }
+
+    #[test]
+    fn test_segments() {
+        let s = |a: (i64, i64), b: (i64, i64)| Segment::new(a.into(), b.into());
+        assert_eq!(
+            s((0, 0), (4, 4)).intersect(&s((0, 3), (3, 0))),
+            SegmentIntersection::Point(Vec2::new(Ratio::new(3, 2), Ratio::new(3, 2)))
+        );
+        assert_eq!(
+            s((0, 0), (1, 1)).intersect(&s((0, 3), (3, 0))),
+            SegmentIntersection::None
+        );
+        assert_eq!(
+            s((0, 0), (4, 2)).intersect(&s((6, 3), (2, 1))),
+            SegmentIntersection::Overlap((2, 1).into(), (4, 2).into())
+        );
+        assert_eq!(
+            s((0, 0), (2, 1)).intersect(&s((4, 2), (2, 1))),
+            SegmentIntersection::Point(Vec2::new(Ratio::from(2i64), Ratio::ONE))
+        );
+        assert_eq!(
+            s((0, 0), (2, 1)).intersect(&s((4, 2), (6, 3))),
+            SegmentIntersection::None
+        );
+        assert_eq!(
+            s((1, 1), (1, 1)).intersect(&s((0, 0), (2, 2))),
+            SegmentIntersection::Point(Vec2::new(Ratio::ONE, Ratio::ONE))
+        );
+        assert_eq!(
+            s((1, 0), (1, 0)).intersect(&s((0, 0), (2, 2))),
+            SegmentIntersection::None
+        );
+    }
+
+    #[test]
+    fn test_closest_approach() {
+        let a = Line3::new(Vec3::new(0, 0, 0), Vec3::new(1, 0, 0));
+        let b = Line3::new(Vec3::new(3, -2, 5), Vec3::new(0, 1, 0));
+        assert_eq!(
+            a.closest_approach(&b),
+            Some((Ratio::from(3i64), Ratio::from(2i64)))
+        );
+        assert_eq!(a.intersect(&b), None);
+        let c = Line3::new(Vec3::new(3, -2, 0), Vec3::new(0, 2, 0));
+        assert_eq!(
+            a.intersect(&c),
+            Some(Vec3::new(Ratio::from(3i64), Ratio::ZERO, Ratio::ZERO))
+        );
+        assert_eq!(a.closest_approach(&a), None);
+
+        // The offset between the points does not fit in an i64.
+        let d = Line3::new(Vec3::new(i64::MAX, 0, 0), Vec3::new(0, 1, 0));
+        let e = Line3::new(Vec3::new(i64::MIN, 0, 0), Vec3::new(0, 0, 1));
+        assert_eq!(d.closest_approach(&e), Some((Ratio::ZERO, Ratio::ZERO)));
+    }
+}
@@ -1,14 +1,20 @@
 use std::fmt::{Debug, Display};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use foldhash::fast::FixedState;
+use hashbrown::HashMap;
 
 use winnow::ascii::line_ending;
-use winnow::error::ParserError;
+use winnow::error::{AddContext, ParserError, StrContext, StrContextValue};
 use winnow::stream::{Compare, Stream, StreamIsPartial};
-use winnow::Parser;
+use winnow::{PResult, Parser};
 
-use super::{list, many, Vec2};
+use super::{anychar, list, many, Direction, Vec2};
+use crate::{AoCError, Result};
 
-type DisplayFn<T> = Box<dyn Fn(Vec2<isize>, &T) -> String>;
+pub(super) type DisplayFn<T> = Box<dyn Fn(Vec2<isize>, &T) -> String>;
 
 pub struct Grid<T> {
     pub width: usize,
@@ -50,27 +56,94 @@ impl<T: Clone> Grid<T> {
 }
 
 impl<T> Grid<T> {
+    /// Parse a grid with one row per line, failing if the rows do not all
+    /// have the same width.
     pub fn parse<I, E, P>(parser: P) -> impl Parser<I, Grid<T>, E>
     where
         I: StreamIsPartial + Stream + Compare<&'static str>,
         P: Parser<I, T, E>,
-        E: ParserError<I>,
+        E: ParserError<I> + AddContext<I, StrContext>,
     {
-        list(many(parser), line_ending).map(|data| {
+        let parser = list(many(parser), line_ending).verify_map(|data: Vec<Vec<T>>| {
             let height = data.len();
             let width = data.first().map_or(0, |row| row.len());
-            for row in &data {
-                assert_eq!(row.len(), width);
+            if data.iter().any(|row| row.len() != width) {
+                return None;
             }
             let data = data.into_iter().flatten().collect();
-            Grid {
+            Some(Grid {
                 width,
                 height,
                 data,
                 display_fn: None,
-            }
+            })
+        });
+        parser.context(StrContext::Expected(StrContextValue::Description(
+            "rows of equal width",
+        )))
+    }
+
+    /// Parse a grid whose cells may carry a marker, such as the start and end
+    /// of a maze. The positions of all markers are collected per marker.
+    pub fn parse_with_markers<I, E, P, M>(
+        parser: P,
+    ) -> impl Parser<I, (Grid<T>, HashMap<M, Vec<Vec2<isize>>>), E>
+    where
+        I: StreamIsPartial + Stream + Compare<&'static str>,
+        P: Parser<I, (T, Option<M>), E>,
+        E: ParserError<I> + AddContext<I, StrContext>,
+        M: Eq + Hash,
+    {
+        Grid::parse(parser).map(|grid: Grid<(T, Option<M>)>| {
+            let width = grid.width;
+            let mut markers = HashMap::<M, Vec<_>>::new();
+            let data = grid
+                .data
+                .into_vec()
+                .into_iter()
+                .enumerate()
+                .map(|(i, (cell, marker))| {
+                    if let Some(marker) = marker {
+                        let pos = Vec2::new((i % width) as isize, (i / width) as isize);
+                        markers.entry(marker).or_default().push(pos);
+                    }
+                    cell
+                })
+                .collect();
+            (
+                Grid {
+                    width,
+                    height: grid.height,
+                    data,
+                    display_fn: None,
+                },
+                markers,
+            )
         })
     }
+
+    /// Parse a grid from a string, mapping every character to a cell with
+    /// `f`. Trailing whitespace is ignored.
+    pub fn from_str_with<F>(input: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut input = input.trim_end();
+        let grid: PResult<_> = Grid::parse(anychar.verify_map(&mut f)).parse_next(&mut input);
+        let grid = grid?;
+        if !input.is_empty() {
+            Err(format!("Unexpected input after grid: {:?}", input))?;
+        }
+        Ok(grid)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = AoCError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Grid::from_str_with(s, |c| (!c.is_whitespace()).then_some(c))
+    }
 }
 
 impl<T> Grid<T> {
@@ -167,6 +240,298 @@ impl<T> Grid<T> {
     }
 }
 
+impl<T> Grid<T> {
+    /// Step from `pos` in direction `dir`, if the result lies inside the grid.
+    pub fn step<C, D>(&self, pos: C, dir: D) -> Option<Vec2<isize>>
+    where
+        C: Into<Vec2<isize>>,
+        D: Into<Vec2<isize>>,
+    {
+        let next = pos.into() + dir.into();
+        self.contains(next).then_some(next)
+    }
+
+    /// Step from `pos` in direction `dir`, wrapping around the edges of the
+    /// grid.
+    pub fn step_wrapping<C, D>(&self, pos: C, dir: D) -> Vec2<isize>
+    where
+        C: Into<Vec2<isize>>,
+        D: Into<Vec2<isize>>,
+    {
+        self.wrap(pos.into() + dir.into())
+    }
+
+    fn neighbors<C, I>(&self, pos: C, dirs: I) -> impl Iterator<Item = Vec2<isize>> + '_
+    where
+        C: Into<Vec2<isize>>,
+        I: Iterator<Item = Direction> + 'static,
+    {
+        let pos = pos.into();
+        dirs.filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// All in-bounds cardinal neighbors of `pos`.
+    pub fn cardinal_neighbors<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        self.neighbors(pos, Direction::cardinal())
+    }
+
+    /// All in-bounds cardinal and ordinal neighbors of `pos`.
+    pub fn all_neighbors<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        self.neighbors(pos, Direction::all())
+    }
+
+    /// All in-bounds cardinal neighbors of `pos`, together with their values.
+    pub fn cardinal_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &T)> + '_ {
+        self.cardinal_neighbors(pos).map(|p| (p, &self[p]))
+    }
+
+    /// All in-bounds cardinal and ordinal neighbors of `pos`, together with
+    /// their values.
+    pub fn all_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &T)> + '_ {
+        self.all_neighbors(pos).map(|p| (p, &self[p]))
+    }
+
+    /// Walk from `pos` in direction `dir` until the edge of the grid. The
+    /// starting position itself is not included.
+    pub fn ray<C, D>(&self, pos: C, dir: D) -> impl Iterator<Item = Vec2<isize>> + '_
+    where
+        C: Into<Vec2<isize>>,
+        D: Into<Vec2<isize>>,
+    {
+        let dir = dir.into();
+        std::iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+    }
+
+    /// Find the first position seen from `pos` when looking in direction
+    /// `dir` whose value matches `pred`.
+    pub fn line_of_sight<C, D, P>(&self, pos: C, dir: D, mut pred: P) -> Option<Vec2<isize>>
+    where
+        C: Into<Vec2<isize>>,
+        D: Into<Vec2<isize>>,
+        P: FnMut(&T) -> bool,
+    {
+        self.ray(pos, dir).find(|&p| pred(&self[p]))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Rearrange the cells in place into a `width` x `height` grid, where the
+    /// cell at index `i` is taken from index `src(i)` of the old grid.
+    fn permute<F: Fn(usize) -> usize>(&mut self, width: usize, height: usize, src: F) {
+        let mut done = vec![false; self.data.len()];
+        for start in 0..self.data.len() {
+            let mut i = start;
+            while !done[i] {
+                done[i] = true;
+                let j = src(i);
+                if j == start {
+                    break;
+                }
+                self.data.swap(i, j);
+                i = j;
+            }
+        }
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Mirror the grid in place along its main diagonal.
+    pub fn transpose(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.permute(height, width, |i| (i % height) * width + i / height);
+    }
+
+    /// Rotate the grid in place by 90 degrees clockwise.
+    pub fn rotate_cw(&mut self) {
+        self.transpose();
+        self.flip_horizontal();
+    }
+
+    /// Rotate the grid in place by 90 degrees counterclockwise.
+    pub fn rotate_ccw(&mut self) {
+        self.transpose();
+        self.flip_vertical();
+    }
+
+    /// Mirror the grid in place left to right.
+    pub fn flip_horizontal(&mut self) {
+        for row in self.rows_mut() {
+            row.reverse();
+        }
+    }
+
+    /// Mirror the grid in place top to bottom.
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.transpose();
+        grid
+    }
+
+    pub fn rotated_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.rotate_cw();
+        grid
+    }
+
+    pub fn rotated_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.rotate_ccw();
+        grid
+    }
+
+    pub fn flipped_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.flip_horizontal();
+        grid
+    }
+
+    pub fn flipped_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.flip_vertical();
+        grid
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        self.data.chunks_mut(self.width.max(1))
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        if row < self.height {
+            Some(&mut self.data[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
+        (0..self.width).filter_map(|x| self.get_column(x))
+    }
+
+    pub fn columns_mut(&mut self) -> impl Iterator<Item = Vec<&mut T>> + '_ {
+        let mut columns = (0..self.width)
+            .map(|_| Vec::with_capacity(self.height))
+            .collect::<Vec<_>>();
+        for (i, cell) in self.data.iter_mut().enumerate() {
+            columns[i % self.width].push(cell);
+        }
+        columns.into_iter()
+    }
+
+    pub fn column_mut(&mut self, column: usize) -> Option<impl Iterator<Item = &mut T> + '_> {
+        if column < self.width {
+            Some(self.data.iter_mut().skip(column).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Insert a row before row `index`, shifting all rows after it down.
+    ///
+    /// Panics if the row does not have the width of the grid.
+    pub fn insert_row<I: IntoIterator<Item = T>>(&mut self, index: usize, row: I) {
+        assert!(index <= self.height, "Row index out of bounds");
+        let mut data = std::mem::take(&mut self.data).into_vec();
+        let len = data.len();
+        data.splice(index * self.width..index * self.width, row);
+        assert_eq!(data.len() - len, self.width, "Row has the wrong width");
+        self.data = data.into_boxed_slice();
+        self.height += 1;
+    }
+
+    /// Remove row `index`, shifting all rows after it up.
+    pub fn remove_row(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.height, "Row index out of bounds");
+        let mut data = std::mem::take(&mut self.data).into_vec();
+        let row = data
+            .drain(index * self.width..(index + 1) * self.width)
+            .collect();
+        self.data = data.into_boxed_slice();
+        self.height -= 1;
+        row
+    }
+
+    /// Insert a column before column `index`, shifting all columns after it
+    /// to the right.
+    ///
+    /// Panics if the column does not have the height of the grid.
+    pub fn insert_column<I: IntoIterator<Item = T>>(&mut self, index: usize, column: I) {
+        assert!(index <= self.width, "Column index out of bounds");
+        let mut column = column.into_iter();
+        let mut old = std::mem::take(&mut self.data).into_vec().into_iter();
+        let mut data = Vec::with_capacity((self.width + 1) * self.height);
+        for _ in 0..self.height {
+            data.extend(old.by_ref().take(index));
+            data.push(column.next().expect("Column has the wrong height"));
+            data.extend(old.by_ref().take(self.width - index));
+        }
+        assert!(column.next().is_none(), "Column has the wrong height");
+        self.data = data.into_boxed_slice();
+        self.width += 1;
+    }
+
+    /// Remove column `index`, shifting all columns after it to the left.
+    pub fn remove_column(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.width, "Column index out of bounds");
+        let mut column = Vec::with_capacity(self.height);
+        let data = std::mem::take(&mut self.data)
+            .into_vec()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, cell)| {
+                if i % self.width == index {
+                    column.push(cell);
+                    None
+                } else {
+                    Some(cell)
+                }
+            })
+            .collect();
+        self.data = data;
+        self.width -= 1;
+        column
+    }
+}
+
 impl<T> Index<Vec2<isize>> for Grid<T> {
     type Output = T;
 
@@ -203,22 +568,34 @@ impl<T> IndexMut<Coordinate> for Grid<T> {
     }
 }
 
+impl<T> IntoIterator for Grid<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_vec().into_iter()
+    }
+}
+
+impl<T: Display> Grid<T> {
+    /// The text of the cell at `pos`, using the display function if set.
+    pub(super) fn display_cell(&self, pos: Vec2<isize>) -> String {
+        match &self.display_fn {
+            Some(display_fn) => display_fn(pos, &self[pos]),
+            None => self[pos].to_string(),
+        }
+    }
+}
+
 impl<T: Display> Display for Grid<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.height {
             for x in 0..self.width {
-                if let Some(display_fn) = &self.display_fn {
-                    write!(
-                        f,
-                        "{}",
-                        display_fn(
-                            Vec2::new(x as isize, y as isize),
-                            &self[Coordinate(Vec2::new(x, y))]
-                        )
-                    )?;
-                } else {
-                    write!(f, "{}", self[Coordinate(Vec2::new(x, y))])?;
-                }
+                write!(
+                    f,
+                    "{}",
+                    self.display_cell(Vec2::new(x as isize, y as isize))
+                )?;
             }
             writeln!(f)?;
         }
@@ -248,3 +625,168 @@ impl<T: Clone> Clone for Grid<T> {
         }
     }
 }
+
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.data == other.data
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.data.hash(state);
+    }
+}
+
+impl<T: Hash> Grid<T> {
+    /// A cheap 64-bit fingerprint of the grid, for cycle detection on large
+    /// grids where hashing or storing full states is too slow. Different grids
+    /// may collide, though this is unlikely.
+    pub fn fingerprint(&self) -> u64 {
+        FixedState::default().hash_one(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{one_of, HashSet, Itertools};
+
+    fn grid(mut input: &str) -> Grid<char> {
+        let grid: PResult<_> =
+            Grid::parse(one_of(|c: char| !c.is_whitespace())).parse_next(&mut input);
+        grid.unwrap()
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let mut input = "...\n..\n...";
+        let grid: PResult<_> =
+            Grid::parse(one_of(|c: char| !c.is_whitespace())).parse_next(&mut input);
+        let err = grid.unwrap_err().into_inner().unwrap();
+        assert!(err.to_string().contains("expected rows of equal width"));
+        assert!("...\n..".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let mut input = "S.#\n.#E\n..#\n";
+        let parsed: PResult<_> =
+            Grid::parse_with_markers(one_of(['.', '#', 'S', 'E']).map(|c| match c {
+                'S' | 'E' => (false, Some(c)),
+                c => (c == '#', None),
+            }))
+            .parse_next(&mut input);
+        let (walls, markers) = parsed.unwrap();
+        assert_eq!((walls.width, walls.height), (3, 3));
+        assert!(walls[Vec2::new(2, 0)] && !walls[Vec2::new(2, 1)]);
+        assert_eq!(markers[&'S'], [Vec2::new(0, 0)]);
+        assert_eq!(markers[&'E'], [Vec2::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = "ab\ncd\n".parse::<Grid<char>>().unwrap();
+        assert_eq!(to_string(&grid), "ab/cd");
+        let digits = Grid::from_str_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits[Vec2::new(1, 1)], 4);
+        assert!(Grid::from_str_with("12\n3x", |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        let a = grid("#.\n.#");
+        let b = grid("#.\n.#").with_display_fn(|_, _| "x".to_string());
+        assert!(a == b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a, grid("#.#."));
+        assert_ne!(a.fingerprint(), grid(".#\n#.").fingerprint());
+
+        let mut seen = HashSet::new();
+        assert!(seen.insert(a.clone()));
+        assert!(!seen.insert(b));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid("...\n...\n...");
+        assert_eq!(grid.cardinal_neighbors(Vec2::new(0, 0)).count(), 2);
+        assert_eq!(grid.all_neighbors(Vec2::new(0, 0)).count(), 3);
+        assert_eq!(grid.cardinal_neighbors(Vec2::new(1, 1)).count(), 4);
+        assert_eq!(grid.all_neighbors(Vec2::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = grid("...\n...");
+        assert_eq!(grid.step(Vec2::new(0, 0), Direction::North), None);
+        assert_eq!(
+            grid.step(Vec2::new(0, 0), Direction::South),
+            Some(Vec2::new(0, 1))
+        );
+        assert_eq!(
+            grid.step_wrapping(Vec2::new(0, 0), Direction::NorthWest),
+            Vec2::new(2, 1)
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let grid = grid("#....#.");
+        let ray = grid
+            .ray(Vec2::new(1, 0), Direction::East)
+            .collect::<Vec<_>>();
+        assert_eq!(ray.len(), 5);
+        assert_eq!(
+            grid.line_of_sight(Vec2::new(1, 0), Direction::East, |&c| c == '#'),
+            Some(Vec2::new(5, 0))
+        );
+        assert_eq!(
+            grid.line_of_sight(Vec2::new(6, 0), Direction::East, |&c| c == '#'),
+            None
+        );
+    }
+
+    fn to_string(grid: &Grid<char>) -> String {
+        grid.rows()
+            .map(|row| row.iter().collect::<String>())
+            .join("/")
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid("abc\ndef");
+        assert_eq!(to_string(&grid.transposed()), "ad/be/cf");
+        assert_eq!(to_string(&grid.rotated_cw()), "da/eb/fc");
+        assert_eq!(to_string(&grid.rotated_ccw()), "cf/be/ad");
+        assert_eq!(to_string(&grid.flipped_horizontal()), "cba/fed");
+        assert_eq!(to_string(&grid.flipped_vertical()), "def/abc");
+
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated.rotate_cw();
+        }
+        assert_eq!(to_string(&rotated), "abc/def");
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut grid = grid("abc\ndef");
+        grid.insert_row(1, ['x', 'y', 'z']);
+        assert_eq!(to_string(&grid), "abc/xyz/def");
+        grid.insert_column(3, ['1', '2', '3']);
+        assert_eq!(to_string(&grid), "abc1/xyz2/def3");
+        assert_eq!(grid.remove_column(0), ['a', 'x', 'd']);
+        assert_eq!(grid.remove_row(0), ['b', 'c', '1']);
+        assert_eq!(to_string(&grid), "yz2/ef3");
+
+        for mut column in grid.columns_mut() {
+            *column[0] = '.';
+        }
+        assert_eq!(to_string(&grid), ".../ef3");
+    }
+}
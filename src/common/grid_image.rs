@@ -0,0 +1,210 @@
+use std::path::Path;
+
+use super::{Grid, Vec2};
+
+/// A color with red, green and blue components.
+pub type Rgb = [u8; 3];
+
+impl<T> Grid<T> {
+    /// Render the grid as a binary PBM image, where cells for which `black`
+    /// holds are black. Every cell becomes a `scale` by `scale` block.
+    pub fn to_pbm<F>(&self, mut black: F, scale: usize) -> Vec<u8>
+    where
+        F: FnMut(Vec2<isize>, &T) -> bool,
+    {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut image = format!("P4\n{} {}\n", width, height).into_bytes();
+        for y in 0..self.height {
+            let mut row = vec![0u8; width.div_ceil(8)];
+            for x in 0..self.width {
+                let pos = Vec2::new(x as isize, y as isize);
+                if black(pos, &self[pos]) {
+                    for px in x * scale..(x + 1) * scale {
+                        row[px / 8] |= 0x80 >> (px % 8);
+                    }
+                }
+            }
+            for _ in 0..scale {
+                image.extend_from_slice(&row);
+            }
+        }
+        image
+    }
+
+    /// Render the grid as a binary PPM image, coloring every cell with
+    /// `palette`. Every cell becomes a `scale` by `scale` block.
+    pub fn to_ppm<F>(&self, palette: F, scale: usize) -> Vec<u8>
+    where
+        F: FnMut(Vec2<isize>, &T) -> Rgb,
+    {
+        let mut image =
+            format!("P6\n{} {}\n255\n", self.width * scale, self.height * scale).into_bytes();
+        for row in self.pixel_rows(palette, scale) {
+            image.extend_from_slice(&row);
+        }
+        image
+    }
+
+    /// Render the grid as a PNG image, coloring every cell with `palette`.
+    /// Every cell becomes a `scale` by `scale` block. The image data is
+    /// stored without compression.
+    pub fn to_png<F>(&self, palette: F, scale: usize) -> Vec<u8>
+    where
+        F: FnMut(Vec2<isize>, &T) -> Rgb,
+    {
+        let mut raw = Vec::new();
+        for row in self.pixel_rows(palette, scale) {
+            // Every scanline starts with its filter type, which is none.
+            raw.push(0);
+            raw.extend_from_slice(&row);
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&((self.width * scale) as u32).to_be_bytes());
+        header.extend_from_slice(&((self.height * scale) as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, no filtering, no interlacing.
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut image = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut image, b"IHDR", &header);
+        png_chunk(&mut image, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut image, b"IEND", &[]);
+        image
+    }
+
+    /// Write the grid as an image, choosing the format from the extension of
+    /// `path`. For PBM images, cells with a dark color are black.
+    pub fn save_image<P, F>(&self, path: P, mut palette: F, scale: usize) -> std::io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Vec2<isize>, &T) -> Rgb,
+    {
+        let path = path.as_ref();
+        let image = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(palette, scale),
+            Some("ppm") => self.to_ppm(palette, scale),
+            Some("pbm") => self.to_pbm(
+                |pos, cell| {
+                    let [r, g, b] = palette(pos, cell);
+                    (r as u32 + g as u32 + b as u32) < 384
+                },
+                scale,
+            ),
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Unsupported image format: {}", path.display()),
+                ))
+            }
+        };
+        std::fs::write(path, image)
+    }
+
+    /// The RGB bytes of every pixel row of the upscaled image.
+    fn pixel_rows<F>(&self, mut palette: F, scale: usize) -> Vec<Vec<u8>>
+    where
+        F: FnMut(Vec2<isize>, &T) -> Rgb,
+    {
+        let mut rows = Vec::with_capacity(self.height * scale);
+        for y in 0..self.height {
+            let mut row = Vec::with_capacity(self.width * scale * 3);
+            for x in 0..self.width {
+                let pos = Vec2::new(x as isize, y as isize);
+                let color = palette(pos, &self[pos]);
+                for _ in 0..scale {
+                    row.extend_from_slice(&color);
+                }
+            }
+            for _ in 0..scale {
+                rows.push(row.clone());
+            }
+        }
+        rows
+    }
+}
+
+fn png_chunk(image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    image.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = image.len();
+    image.extend_from_slice(kind);
+    image.extend_from_slice(data);
+    let crc = crc32(&image[start..]);
+    image.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        stream.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+        }
+        *entry = c;
+    }
+    !data.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_images() {
+        let grid = Grid::from_data(2, 1, Box::new([true, false]));
+        let palette = |_, &on: &bool| if on { [0, 0, 0] } else { [255, 255, 255] };
+
+        assert_eq!(grid.to_pbm(|_, &on| on, 2), b"P4\n4 2\n\xc0\xc0");
+        let ppm = grid.to_ppm(palette, 1);
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\x00\xff\xff\xff");
+
+        let png = grid.to_png(palette, 3);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 6, 0, 0, 0, 3]);
+        // Three scanlines of a filter byte and six RGB pixels, stored in one
+        // block after the zlib header.
+        let raw_len = 3 * (1 + 6 * 3);
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(idat_len, 2 + 5 + raw_len + 4);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+}
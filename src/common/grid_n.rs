@@ -0,0 +1,258 @@
+use std::ops::{Index, IndexMut};
+
+use super::Grid;
+
+/// A dense grid in `D` dimensions. Positions are `[isize; D]` and the bounds
+/// may start at any position, so the grid can grow in every direction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridN<T, const D: usize> {
+    min: [isize; D],
+    size: [usize; D],
+    data: Vec<T>,
+    default: T,
+}
+
+impl<T: Clone, const D: usize> GridN<T, D> {
+    /// A grid of `size` starting at the origin, filled with `default`.
+    pub fn new(size: [usize; D], default: T) -> Self {
+        Self::with_bounds([0; D], size.map(|s| s as isize - 1), default)
+    }
+
+    /// A grid spanning the inclusive bounds from `min` to `max`, filled with
+    /// `default`.
+    pub fn with_bounds(min: [isize; D], max: [isize; D], default: T) -> Self {
+        let size = std::array::from_fn(|i| (max[i] - min[i] + 1).max(0) as usize);
+        Self {
+            min,
+            size,
+            data: vec![default.clone(); size.iter().product()],
+            default,
+        }
+    }
+
+    /// Embed a 2D grid in the plane spanned by the first two axes, with all
+    /// other coordinates zero.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self {
+        assert!(D >= 2, "a 2D grid needs at least two dimensions");
+        let mut size = [1; D];
+        size[0] = grid.width;
+        size[1] = grid.height;
+        let mut grid_n = Self::new(size, default);
+        for pos in grid.coordinates() {
+            let mut p = [0; D];
+            p[0] = pos.x;
+            p[1] = pos.y;
+            grid_n[p] = grid[pos].clone();
+        }
+        grid_n
+    }
+
+    /// Grow the bounds by `n` cells on every side, filling new cells with the
+    /// default value.
+    pub fn grow(&mut self, n: usize) {
+        let n = n as isize;
+        let max = self.max();
+        self.resize(self.min.map(|m| m - n), max.map(|m| m + n));
+    }
+
+    /// Grow the bounds just enough to contain `pos`.
+    pub fn grow_to_include(&mut self, pos: [isize; D]) {
+        if self.contains(pos) {
+            return;
+        }
+        let max = self.max();
+        self.resize(
+            std::array::from_fn(|i| self.min[i].min(pos[i])),
+            std::array::from_fn(|i| max[i].max(pos[i])),
+        );
+    }
+
+    fn resize(&mut self, min: [isize; D], max: [isize; D]) {
+        let mut grown = Self::with_bounds(min, max, self.default.clone());
+        for pos in self.coordinates() {
+            grown[pos] = self[pos].clone();
+        }
+        *self = grown;
+    }
+
+    /// The 2D cross-section along axes `x` and `y` through `at`, where the
+    /// coordinates of `at` along `x` and `y` are ignored.
+    pub fn slice(&self, x: usize, y: usize, at: [isize; D]) -> Grid<T> {
+        let (width, height) = (self.size[x], self.size[y]);
+        let data = (0..height)
+            .flat_map(|j| (0..width).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let mut pos = at;
+                pos[x] = self.min[x] + i as isize;
+                pos[y] = self.min[y] + j as isize;
+                self.get(pos).unwrap_or(&self.default).clone()
+            })
+            .collect();
+        Grid::from_data(width, height, data)
+    }
+}
+
+impl<T, const D: usize> GridN<T, D> {
+    /// Inclusive minimum of the bounds.
+    pub fn min(&self) -> [isize; D] {
+        self.min
+    }
+
+    /// Inclusive maximum of the bounds.
+    pub fn max(&self) -> [isize; D] {
+        std::array::from_fn(|i| self.min[i] + self.size[i] as isize - 1)
+    }
+
+    pub fn size(&self) -> [usize; D] {
+        self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn contains(&self, pos: [isize; D]) -> bool {
+        (0..D).all(|i| pos[i] >= self.min[i] && pos[i] < self.min[i] + self.size[i] as isize)
+    }
+
+    fn index_of(&self, pos: [isize; D]) -> Option<usize> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let mut index = 0;
+        for i in (0..D).rev() {
+            index = index * self.size[i] + (pos[i] - self.min[i]) as usize;
+        }
+        Some(index)
+    }
+
+    fn position_of(&self, mut index: usize) -> [isize; D] {
+        std::array::from_fn(|i| {
+            let c = index % self.size[i];
+            index /= self.size[i];
+            self.min[i] + c as isize
+        })
+    }
+
+    pub fn get(&self, pos: [isize; D]) -> Option<&T> {
+        Some(&self.data[self.index_of(pos)?])
+    }
+
+    pub fn get_mut(&mut self, pos: [isize; D]) -> Option<&mut T> {
+        let index = self.index_of(pos)?;
+        Some(&mut self.data[index])
+    }
+
+    pub fn set(&mut self, pos: [isize; D], value: T) {
+        if let Some(cell) = self.get_mut(pos) {
+            *cell = value;
+        }
+    }
+
+    /// All positions within the bounds, with the first axis changing fastest.
+    pub fn coordinates(&self) -> impl Iterator<Item = [isize; D]> + '_ {
+        (0..self.len()).map(|i| self.position_of(i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ([isize; D], &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.position_of(i), value))
+    }
+
+    pub fn map<U, F: FnMut([isize; D], &T) -> U>(&self, default: U, mut f: F) -> GridN<U, D> {
+        GridN {
+            min: self.min,
+            size: self.size,
+            data: self.iter().map(|(pos, value)| f(pos, value)).collect(),
+            default,
+        }
+    }
+
+    /// The `2 * D` neighbors of `pos` that share a face with it and lie within
+    /// the bounds.
+    pub fn face_neighbors(&self, pos: [isize; D]) -> impl Iterator<Item = [isize; D]> + '_ {
+        (0..D)
+            .flat_map(|axis| [-1, 1].map(|d| (axis, d)))
+            .map(move |(axis, d)| {
+                let mut next = pos;
+                next[axis] += d;
+                next
+            })
+            .filter(|&next| self.contains(next))
+    }
+
+    /// The `3^D - 1` neighbors of `pos` that share at least a corner with it
+    /// and lie within the bounds.
+    pub fn all_neighbors(&self, pos: [isize; D]) -> impl Iterator<Item = [isize; D]> + '_ {
+        (0..3usize.pow(D as u32))
+            .map(move |mut k| {
+                std::array::from_fn(|i| {
+                    let d = (k % 3) as isize - 1;
+                    k /= 3;
+                    pos[i] + d
+                })
+            })
+            .filter(move |&next| next != pos && self.contains(next))
+    }
+}
+
+impl<T, const D: usize> Index<[isize; D]> for GridN<T, D> {
+    type Output = T;
+
+    fn index(&self, index: [isize; D]) -> &Self::Output {
+        self.get(index).expect("position out of bounds")
+    }
+}
+
+impl<T, const D: usize> IndexMut<[isize; D]> for GridN<T, D> {
+    fn index_mut(&mut self, index: [isize; D]) -> &mut Self::Output {
+        self.get_mut(index).expect("position out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Vec2;
+
+    #[test]
+    fn test_neighbors() {
+        let grid = GridN::new([3, 3, 3, 3], false);
+        assert_eq!(grid.len(), 81);
+        assert_eq!(grid.face_neighbors([1, 1, 1, 1]).count(), 8);
+        assert_eq!(grid.all_neighbors([1, 1, 1, 1]).count(), 80);
+        assert_eq!(grid.all_neighbors([0, 0, 0, 0]).count(), 15);
+    }
+
+    #[test]
+    fn test_grow_and_slice() {
+        let plane = Grid::from_data(2, 2, Box::new([1, 2, 3, 4]));
+        let mut grid = GridN::<_, 3>::from_grid(&plane, 0);
+        assert_eq!(grid.size(), [2, 2, 1]);
+        grid.grow(1);
+        assert_eq!((grid.min(), grid.max()), ([-1, -1, -1], [2, 2, 1]));
+        assert_eq!(grid[[1, 1, 0]], 4);
+        grid.grow_to_include([0, 0, 5]);
+        grid[[0, 0, 5]] = 7;
+        assert_eq!(grid.max(), [2, 2, 5]);
+
+        let slice = grid.slice(0, 2, [0, 0, 0]);
+        assert_eq!((slice.width, slice.height), (4, 7));
+        assert_eq!(slice[Vec2::new(1, 1)], 1);
+        assert_eq!(slice[Vec2::new(1, 6)], 7);
+        assert_eq!(grid.slice(0, 1, [0, 0, 0]), {
+            let mut expected = Grid::new(4, 4);
+            expected[Vec2::new(1, 1)] = 1;
+            expected[Vec2::new(2, 1)] = 2;
+            expected[Vec2::new(1, 2)] = 3;
+            expected[Vec2::new(2, 2)] = 4;
+            expected
+        });
+    }
+}
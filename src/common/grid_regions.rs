@@ -0,0 +1,200 @@
+use hashbrown::HashSet;
+
+use super::{Direction, Grid, Vec2};
+
+/// A connected group of cells, see [`Grid::regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    pub area: usize,
+    /// Number of cell edges on the boundary of the region.
+    pub perimeter: usize,
+    /// Number of straight sides of the boundary of the region.
+    pub sides: usize,
+    /// Inclusive bounding box of the region.
+    pub min: Vec2<isize>,
+    pub max: Vec2<isize>,
+}
+
+/// The labeling of a grid into regions. Cells that are not part of any region
+/// have no label.
+pub struct Regions {
+    pub labels: Grid<Option<usize>>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// All positions that belong to region `label`.
+    pub fn cells(&self, label: usize) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        self.labels
+            .coordinates()
+            .filter(move |&pos| self.labels[pos] == Some(label))
+    }
+}
+
+impl<T> Grid<T> {
+    /// All positions reachable from `start` with cardinal moves, only
+    /// entering cells for which `pred` holds.
+    pub fn flood_fill<C, P>(&self, start: C, mut pred: P) -> HashSet<Vec2<isize>>
+    where
+        C: Into<Vec2<isize>>,
+        P: FnMut(Vec2<isize>, &T) -> bool,
+    {
+        let start = start.into();
+        let mut visited = HashSet::new();
+        if !self.contains(start) || !pred(start, &self[start]) {
+            return visited;
+        }
+
+        let mut stack = vec![start];
+        visited.insert(start);
+        while let Some(pos) = stack.pop() {
+            for next in self.cardinal_neighbors(pos) {
+                if !visited.contains(&next) && pred(next, &self[next]) {
+                    visited.insert(next);
+                    stack.push(next);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Split the grid into regions of cardinally connected, equal cells.
+    pub fn regions(&self) -> Regions
+    where
+        T: PartialEq,
+    {
+        self.regions_by(|_, _| true, |a, b| a == b)
+    }
+
+    /// Split the cells for which `include` holds into regions, where two
+    /// neighboring cells belong to the same region if `connected` holds.
+    pub fn regions_by<P, F>(&self, mut include: P, mut connected: F) -> Regions
+    where
+        P: FnMut(Vec2<isize>, &T) -> bool,
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut labels = Grid::new_default(self.width, self.height, None);
+        let mut regions = Vec::new();
+
+        for start in self.coordinates() {
+            if labels[start].is_some() || !include(start, &self[start]) {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut cells = vec![start];
+            labels[start] = Some(label);
+            let mut i = 0;
+            while let Some(&pos) = cells.get(i) {
+                i += 1;
+                for next in self.cardinal_neighbors(pos) {
+                    if labels[next].is_none()
+                        && connected(&self[pos], &self[next])
+                        && include(next, &self[next])
+                    {
+                        labels[next] = Some(label);
+                        cells.push(next);
+                    }
+                }
+            }
+
+            let boundary = |pos: Vec2<isize>, dir: Direction| {
+                labels.get(pos) == Some(&Some(label)) && labels.get(pos + dir) != Some(&Some(label))
+            };
+            let mut region = Region {
+                label,
+                area: cells.len(),
+                perimeter: 0,
+                sides: 0,
+                min: start,
+                max: start,
+            };
+            for &pos in &cells {
+                region.min = Vec2::new(region.min.x.min(pos.x), region.min.y.min(pos.y));
+                region.max = Vec2::new(region.max.x.max(pos.x), region.max.y.max(pos.y));
+                for dir in Direction::cardinal().filter(|&dir| boundary(pos, dir)) {
+                    region.perimeter += 1;
+                    // Only count the edge at one end of every side.
+                    if !boundary(pos + dir.right(), dir) {
+                        region.sides += 1;
+                    }
+                }
+            }
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+
+    /// All cells that are not on a loop but enclosed by it, found with a
+    /// scanline parity test. `on_loop` tells whether a cell is part of the
+    /// loop and `north` whether a loop cell connects to the cell above it.
+    pub fn enclosed_by_loop<L, N>(&self, mut on_loop: L, mut north: N) -> Vec<Vec2<isize>>
+    where
+        L: FnMut(Vec2<isize>, &T) -> bool,
+        N: FnMut(Vec2<isize>, &T) -> bool,
+    {
+        let mut enclosed = Vec::new();
+        for y in 0..self.height as isize {
+            let mut inside = false;
+            for x in 0..self.width as isize {
+                let pos = Vec2::new(x, y);
+                if on_loop(pos, &self[pos]) {
+                    if north(pos, &self[pos]) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    enclosed.push(pos);
+                }
+            }
+        }
+        enclosed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::from_data(
+            rows[0].len(),
+            rows.len(),
+            rows.iter().flat_map(|row| row.chars()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_regions() {
+        let regions = grid(&["AAAA", "BBCD", "BBCC", "EEEC"]).regions();
+        let stats = regions
+            .regions
+            .iter()
+            .map(|r| (r.area, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stats,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(regions.regions[2].min, Vec2::new(2, 1));
+        assert_eq!(regions.regions[2].max, Vec2::new(3, 3));
+        assert_eq!(regions.cells(3).collect::<Vec<_>>(), [Vec2::new(3, 1)]);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = grid(&["..#.", ".##.", "...."]);
+        assert_eq!(grid.flood_fill(Vec2::new(0, 0), |_, &c| c == '.').len(), 9);
+        assert!(grid
+            .flood_fill(Vec2::new(2, 0), |_, &c| c == '.')
+            .is_empty());
+    }
+
+    #[test]
+    fn test_enclosed_by_loop() {
+        let grid = grid(&[".....", ".F-7.", ".|.|.", ".L-J.", "....."]);
+        let enclosed = grid.enclosed_by_loop(|_, &c| c != '.', |_, &c| "|LJ".contains(c));
+        assert_eq!(enclosed, [Vec2::new(2, 2)]);
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::Display;
+use std::io::IsTerminal;
+
+use hashbrown::HashSet;
+
+use super::{Grid, Rgb, Vec2};
+
+/// A set of positions drawn on top of a grid, such as a path or the cells
+/// visited by a search.
+pub struct Overlay {
+    positions: HashSet<Vec2<isize>>,
+    color: Option<Rgb>,
+    glyph: Option<char>,
+}
+
+impl Overlay {
+    pub fn new<I: IntoIterator<Item = Vec2<isize>>>(positions: I) -> Self {
+        Self {
+            positions: positions.into_iter().collect(),
+            color: None,
+            glyph: None,
+        }
+    }
+
+    /// Highlight the cells with a background color.
+    pub fn color(self, color: Rgb) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    /// Replace the text of the cells with `glyph`.
+    pub fn glyph(self, glyph: char) -> Self {
+        Self {
+            glyph: Some(glyph),
+            ..self
+        }
+    }
+}
+
+/// Renders a grid to the terminal with overlays, see [`Grid::render`].
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    overlays: Vec<Overlay>,
+    viewport: Option<(Vec2<isize>, Vec2<isize>)>,
+    ansi: bool,
+}
+
+impl<T: Display> Grid<T> {
+    /// Start rendering the grid. Colors are only emitted if stdout is a
+    /// terminal.
+    pub fn render(&self) -> Renderer<'_, T> {
+        Renderer {
+            grid: self,
+            overlays: Vec::new(),
+            viewport: None,
+            ansi: std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+impl<T: Display> Renderer<'_, T> {
+    /// Draw `overlay` on top of the grid and all earlier overlays.
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Only render the cells at most `radius` away from `center` along each
+    /// axis.
+    pub fn viewport(self, center: Vec2<isize>, radius: Vec2<isize>) -> Self {
+        Self {
+            viewport: Some((center - radius, center + radius)),
+            ..self
+        }
+    }
+
+    /// Force colors on or off, regardless of whether stdout is a terminal.
+    pub fn ansi(self, ansi: bool) -> Self {
+        Self { ansi, ..self }
+    }
+
+    pub fn print(&self) {
+        print!("{}", self);
+    }
+}
+
+impl<T: Display> Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (mut min, mut max) = (
+            Vec2::new(0, 0),
+            Vec2::new(self.grid.width as isize - 1, self.grid.height as isize - 1),
+        );
+        if let Some((from, to)) = self.viewport {
+            min = Vec2::new(min.x.max(from.x), min.y.max(from.y));
+            max = Vec2::new(max.x.min(to.x), max.y.min(to.y));
+        }
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let pos = Vec2::new(x, y);
+                let mut text = None;
+                let mut color = None;
+                for overlay in self.overlays.iter().filter(|o| o.positions.contains(&pos)) {
+                    text = overlay.glyph.map(String::from).or(text);
+                    color = overlay.color.or(color);
+                }
+                let text = text.unwrap_or_else(|| self.grid.display_cell(pos));
+                match color {
+                    Some([r, g, b]) if self.ansi => {
+                        write!(f, "\x1b[48;2;{};{};{}m{}\x1b[0m", r, g, b, text)?
+                    }
+                    _ => write!(f, "{}", text)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::new_default(5, 3, '.');
+        let path = Overlay::new((0..5).map(|x| Vec2::new(x, 1)))
+            .glyph('o')
+            .color([255, 0, 0]);
+        let end = Overlay::new([Vec2::new(4, 1)]).glyph('E');
+
+        let plain = grid.render().overlay(path).overlay(end).ansi(false);
+        assert_eq!(plain.to_string(), ".....\nooooE\n.....\n");
+        let cropped = plain.viewport(Vec2::new(4, 0), Vec2::new(1, 1));
+        assert_eq!(cropped.to_string(), "..\noE\n");
+
+        let start = Overlay::new([Vec2::new(0, 0)]).color([0, 255, 0]);
+        let colored = grid.render().overlay(start).ansi(true).to_string();
+        assert!(colored.starts_with("\x1b[48;2;0;255;0m.\x1b[0m...."));
+    }
+}
@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+use hashbrown::{HashMap, HashSet};
+
+use super::{Direction, Grid, Vec2};
+
+/// Shortest distances from a set of sources to every reachable state, keeping
+/// track of all optimal predecessors of each state.
+pub struct ShortestPaths<S> {
+    distances: HashMap<S, u32>,
+    parents: HashMap<S, Vec<S>>,
+}
+
+impl<S: Copy + Eq + Hash + Ord> ShortestPaths<S> {
+    /// Run Dijkstra's algorithm from all `sources` at once. `successors`
+    /// returns the states reachable from a state, together with the cost of
+    /// the move.
+    pub fn dijkstra<I, F, N>(sources: I, mut successors: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        F: FnMut(&S) -> N,
+        N: IntoIterator<Item = (S, u32)>,
+    {
+        let mut paths = Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+        };
+        let mut queue = BinaryHeap::new();
+        let mut done = HashSet::new();
+        for source in sources {
+            paths.distances.insert(source, 0);
+            queue.push(Reverse((0, source)));
+        }
+
+        while let Some(Reverse((dist, state))) = queue.pop() {
+            if !done.insert(state) {
+                continue;
+            }
+            for (next, cost) in successors(&state) {
+                let next_dist = dist + cost;
+                match paths.distances.get(&next) {
+                    Some(&d) if d < next_dist => continue,
+                    // With zero-cost moves the parents can form cycles, which
+                    // the methods reading them guard against.
+                    Some(&d) if d == next_dist => {
+                        if next != state {
+                            paths.parents.entry(next).or_default().push(state);
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+                paths.distances.insert(next, next_dist);
+                paths.parents.insert(next, vec![state]);
+                queue.push(Reverse((next_dist, next)));
+            }
+        }
+
+        paths
+    }
+
+    /// Run a breadth-first search from all `sources` at once, where every move
+    /// costs one step.
+    pub fn bfs<I, F, N>(sources: I, mut successors: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        F: FnMut(&S) -> N,
+        N: IntoIterator<Item = S>,
+    {
+        let mut paths = Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+        };
+        let mut queue = VecDeque::new();
+        for source in sources {
+            paths.distances.insert(source, 0);
+            queue.push_back(source);
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let next_dist = paths.distances[&state] + 1;
+            for next in successors(&state) {
+                match paths.distances.get(&next) {
+                    Some(&d) if d < next_dist => {}
+                    Some(_) => paths.parents.entry(next).or_default().push(state),
+                    None => {
+                        paths.distances.insert(next, next_dist);
+                        paths.parents.insert(next, vec![state]);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        paths
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u32> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> impl Iterator<Item = (S, u32)> + '_ {
+        self.distances.iter().map(|(&state, &dist)| (state, dist))
+    }
+
+    /// The closest of `targets`, together with its distance.
+    pub fn closest<I: IntoIterator<Item = S>>(&self, targets: I) -> Option<(S, u32)> {
+        targets
+            .into_iter()
+            .filter_map(|target| Some((target, self.distance(&target)?)))
+            .min_by_key(|&(_, dist)| dist)
+    }
+
+    /// One shortest path from a source to `target`, both included.
+    pub fn path(&self, target: S) -> Option<Vec<S>> {
+        self.distance(&target)?;
+        let mut path = vec![target];
+        let mut seen = HashSet::new();
+        seen.insert(target);
+        while let Some(&parent) = self.parents.get(path.last()?).and_then(|p| p.first()) {
+            if !seen.insert(parent) {
+                break;
+            }
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state that lies on some shortest path to the closest of
+    /// `targets`. All targets at that distance are included.
+    pub fn optimal_states<I: IntoIterator<Item = S>>(&self, targets: I) -> HashSet<S> {
+        let targets = targets
+            .into_iter()
+            .filter_map(|target| Some((target, self.distance(&target)?)))
+            .collect::<Vec<_>>();
+        let Some(best) = targets.iter().map(|&(_, dist)| dist).min() else {
+            return HashSet::new();
+        };
+
+        let mut states = HashSet::new();
+        let mut queue = targets
+            .into_iter()
+            .filter(|&(_, dist)| dist == best)
+            .map(|(target, _)| target)
+            .collect::<Vec<_>>();
+        while let Some(state) = queue.pop() {
+            if states.insert(state) {
+                queue.extend(self.parents.get(&state).into_iter().flatten());
+            }
+        }
+        states
+    }
+}
+
+impl<T> Grid<T> {
+    /// Shortest paths over cardinal moves, where `cost(from, to)` gives the
+    /// cost of moving between two neighboring cells, or `None` if the move is
+    /// not possible.
+    pub fn shortest_paths<I, C>(&self, sources: I, mut cost: C) -> ShortestPaths<Vec2<isize>>
+    where
+        I: IntoIterator<Item = Vec2<isize>>,
+        C: FnMut(Vec2<isize>, Vec2<isize>) -> Option<u32>,
+    {
+        ShortestPaths::dijkstra(sources, |&pos| {
+            Direction::cardinal()
+                .filter_map(|dir| {
+                    let next = self.step(pos, dir)?;
+                    Some((next, cost(pos, next)?))
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Number of cardinal steps from the closest of `sources` to every cell,
+    /// only entering cells for which `passable` holds.
+    pub fn bfs<I, P>(&self, sources: I, mut passable: P) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Vec2<isize>>,
+        P: FnMut(Vec2<isize>, &T) -> bool,
+    {
+        let paths = ShortestPaths::bfs(sources, |&pos| {
+            self.cardinal_neighbors(pos)
+                .filter(|&next| passable(next, &self[next]))
+                .collect::<Vec<_>>()
+        });
+        self.distance_field(&paths, |&pos| pos)
+    }
+
+    /// Cheapest cost from the closest of `sources` to every cell, see
+    /// [`Grid::shortest_paths`].
+    pub fn dijkstra<I, C>(&self, sources: I, cost: C) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Vec2<isize>>,
+        C: FnMut(Vec2<isize>, Vec2<isize>) -> Option<u32>,
+    {
+        let paths = self.shortest_paths(sources, cost);
+        self.distance_field(&paths, |&pos| pos)
+    }
+
+    /// Project the distances of a search over arbitrary states (for example a
+    /// position and a direction) onto the grid, keeping the smallest distance
+    /// of all states at the same position.
+    pub fn distance_field<S, F>(&self, paths: &ShortestPaths<S>, pos: F) -> Grid<Option<u32>>
+    where
+        S: Copy + Eq + Hash + Ord,
+        F: Fn(&S) -> Vec2<isize>,
+    {
+        let mut field = Grid::new_default(self.width, self.height, None);
+        for (state, dist) in paths.distances() {
+            if let Some(cell) = field.get_mut(pos(&state)) {
+                *cell = Some(cell.map_or(dist, |d: u32| d.min(dist)));
+            }
+        }
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs() {
+        let walls = Grid::from_data(
+            3,
+            3,
+            Box::new([
+                false, true, false, //
+                false, true, false, //
+                false, false, false,
+            ]),
+        );
+        let field = walls.bfs([Vec2::new(0, 0)], |_, &wall| !wall);
+        assert_eq!(field[Vec2::new(2, 0)], Some(6));
+        assert_eq!(field[Vec2::new(1, 0)], None);
+    }
+
+    #[test]
+    fn test_optimal_states() {
+        let grid = Grid::new_default(3, 3, 1u32);
+        let paths = grid.shortest_paths([Vec2::new(0, 0)], |_, to| Some(grid[to]));
+        assert_eq!(paths.distance(&Vec2::new(2, 2)), Some(4));
+        assert_eq!(paths.path(Vec2::new(2, 2)).map(|p| p.len()), Some(5));
+        assert_eq!(paths.optimal_states([Vec2::new(2, 2)]).len(), 9);
+        assert_eq!(paths.optimal_states([Vec2::new(1, 0)]).len(), 2);
+    }
+
+    #[test]
+    fn test_zero_cost_moves() {
+        // 0 and 1 are connected both ways for free, 2 costs one more.
+        let paths = ShortestPaths::dijkstra([0], |&state: &u8| match state {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![(1, 1)],
+        });
+        assert_eq!(paths.distance(&1), Some(0));
+        assert_eq!(paths.path(1), Some(vec![0, 1]));
+        assert_eq!(paths.path(2), Some(vec![0, 1, 2]));
+        assert_eq!(paths.optimal_states([1]), HashSet::from_iter([0, 1]));
+
+        // 1 is finished before 2, which reaches it again for free.
+        let paths = ShortestPaths::dijkstra([0], |&state: &u8| match state {
+            0 => vec![(2, 1), (1, 1)],
+            2 => vec![(1, 0)],
+            _ => vec![],
+        });
+        assert_eq!(paths.path(1), Some(vec![0, 1]));
+        assert_eq!(paths.optimal_states([1]), HashSet::from_iter([0, 1, 2]));
+    }
+}
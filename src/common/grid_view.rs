@@ -0,0 +1,216 @@
+use std::fmt::Display;
+use std::ops::Index;
+
+use super::{Grid, Vec2};
+
+/// A borrowed rectangle of a grid. Positions are relative to the top left of
+/// the rectangle.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Vec2<isize>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Grid<T> {
+    /// View the rectangle of `width` by `height` cells with its top left at
+    /// `origin`, which must lie within the grid.
+    pub fn view(&self, origin: Vec2<isize>, width: usize, height: usize) -> GridView<'_, T> {
+        assert!(
+            origin.x >= 0
+                && origin.y >= 0
+                && origin.x as usize + width <= self.width
+                && origin.y as usize + height <= self.height,
+            "view out of bounds"
+        );
+        GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// All views of `width` by `height` cells, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        let (xs, ys) = (
+            (self.width + 1).saturating_sub(width),
+            (self.height + 1).saturating_sub(height),
+        );
+        (0..ys).flat_map(move |y| {
+            (0..xs).map(move |x| self.view(Vec2::new(x as isize, y as isize), width, height))
+        })
+    }
+
+    /// The distinct rotations and flips of the grid.
+    pub fn orientations(&self) -> Vec<Grid<T>>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut orientations: Vec<Grid<T>> = Vec::with_capacity(8);
+        let mut grid = self.clone();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                if !orientations.contains(&grid) {
+                    orientations.push(grid.clone());
+                }
+                grid.rotate_cw();
+            }
+            grid.flip_horizontal();
+        }
+        orientations
+    }
+
+    /// The top left positions where `pattern` matches the grid. Cells of the
+    /// pattern that are `None` match anything.
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<Vec2<isize>>
+    where
+        T: PartialEq,
+    {
+        self.windows(pattern.width, pattern.height)
+            .filter(|view| view.matches(pattern))
+            .map(|view| view.origin())
+            .collect()
+    }
+
+    /// Like [`Grid::find_pattern`], but matching each distinct rotation and
+    /// flip of the pattern. Returns the position of every match together with
+    /// the index of the orientation in [`Grid::orientations`].
+    pub fn find_pattern_oriented(&self, pattern: &Grid<Option<T>>) -> Vec<(Vec2<isize>, usize)>
+    where
+        T: Clone + PartialEq,
+    {
+        pattern
+            .orientations()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, pattern)| {
+                self.find_pattern(pattern)
+                    .into_iter()
+                    .map(move |pos| (pos, i))
+            })
+            .collect()
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Position of the top left of the view in the underlying grid.
+    pub fn origin(&self) -> Vec2<isize> {
+        self.origin
+    }
+
+    pub fn contains<C: Into<Vec2<isize>>>(&self, pos: C) -> bool {
+        let pos = pos.into();
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.width as isize && pos.y < self.height as isize
+    }
+
+    pub fn get<C: Into<Vec2<isize>>>(&self, pos: C) -> Option<&'a T> {
+        let pos = pos.into();
+        if !self.contains(pos) {
+            return None;
+        }
+        self.grid.get(self.origin + pos)
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Vec2<isize>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Vec2::new(x, y)))
+    }
+
+    /// All cells of the view row by row, together with their position in the
+    /// view.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2<isize>, &'a T)> + '_ {
+        let view = *self;
+        self.coordinates()
+            .map(move |pos| (pos, view.get(pos).expect("position in view")))
+    }
+
+    /// Whether the view equals `pattern`, where `None` cells match anything.
+    pub fn matches(&self, pattern: &Grid<Option<T>>) -> bool
+    where
+        T: PartialEq,
+    {
+        (self.width, self.height) == (pattern.width, pattern.height)
+            && self
+                .iter()
+                .all(|(pos, value)| pattern[pos].as_ref().is_none_or(|p| p == value))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_data(
+            self.width,
+            self.height,
+            self.iter().map(|(_, value)| value.clone()).collect(),
+        )
+    }
+}
+
+impl<T> Index<Vec2<isize>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2<isize>) -> &Self::Output {
+        self.get(index).expect("position out of view")
+    }
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T: Display> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height as isize {
+            for x in 0..self.width as isize {
+                write!(
+                    f,
+                    "{}",
+                    self.grid.display_cell(self.origin + Vec2::new(x, y))
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::from_data(4, 3, "abcdefghijkl".chars().collect());
+        let view = grid.view(Vec2::new(1, 1), 2, 2);
+        assert_eq!(view[Vec2::new(1, 0)], 'g');
+        assert_eq!(view.get(Vec2::new(2, 0)), None);
+        assert_eq!(view.to_string(), "fg\njk\n");
+        assert_eq!(grid.windows(2, 2).count(), 6);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = Grid::from_data(4, 3, "#.#..#...##.".chars().collect());
+        let pattern = Grid::from_data(2, 2, Box::new([Some('#'), None, None, Some('#')]));
+        assert_eq!(
+            grid.find_pattern(&pattern),
+            [Vec2::new(0, 0), Vec2::new(1, 1)]
+        );
+        assert_eq!(pattern.orientations().len(), 2);
+        assert_eq!(
+            grid.find_pattern_oriented(&pattern),
+            [
+                (Vec2::new(0, 0), 0),
+                (Vec2::new(1, 1), 0),
+                (Vec2::new(1, 0), 1)
+            ]
+        );
+    }
+}
@@ -1,23 +1,53 @@
+mod automaton;
+mod axis_box;
+mod bit_grid;
 mod dir;
+mod dir_set;
+mod geometry;
 mod graph;
 mod grid;
+mod grid_image;
+mod grid_n;
+mod grid_regions;
+mod grid_render;
+mod grid_search;
+mod grid_view;
 mod math;
 mod md5;
+mod ocr;
 mod parse;
+mod polygon;
+mod sparse_grid;
 mod union_find;
 mod vec;
+mod wrapping;
 
 pub use std::collections::VecDeque;
 
+pub use automaton::{Automaton, CellSpace, Cycle, Neighborhood};
+pub use axis_box::{AxisBox, Cuboid, Rect};
+pub use bit_grid::BitGrid;
 pub use dir::Direction;
+pub use dir_set::{DirectionMap, DirectionSet};
+pub use geometry::{Line, Line3, LineIntersection, Ratio, Segment, SegmentIntersection};
 pub use graph::iter_cliques;
 pub use grid::Grid;
+pub use grid_image::Rgb;
+pub use grid_n::GridN;
+pub use grid_regions::{Region, Regions};
+pub use grid_render::{Overlay, Renderer};
+pub use grid_search::ShortestPaths;
+pub use grid_view::GridView;
 pub use hashbrown::{HashMap, HashSet};
 pub use itertools::Itertools;
 pub use math::*;
 pub use md5::MD5;
+pub use ocr::{ocr, ocr_buffer, ocr_str};
 pub use parse::*;
 pub use pathfinding::prelude::*;
+pub use polygon::Polygon;
 pub use rustworkx_core::petgraph::prelude::*;
+pub use sparse_grid::SparseGrid;
 pub use union_find::UnionFind;
-pub use vec::{Vec2, Vec3};
+pub use vec::{Vec2, Vec3, Vec4, VecN, XY, XYZ, XYZW};
+pub use wrapping::WrappingView;
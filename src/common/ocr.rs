@@ -0,0 +1,289 @@
+use super::{Grid, Vec2};
+use crate::Result;
+
+/// The glyphs of the 6 pixel high font.
+const SMALL_FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The glyphs of the 10 pixel high font.
+const LARGE_FONT: [(char, [&str; 10]); 14] = [
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+];
+
+/// Read the capital letters drawn by the lit cells of `grid`. Blank rows
+/// around the text are ignored, and the font is chosen by the height of the
+/// text.
+pub fn ocr(grid: &Grid<bool>) -> Result<String> {
+    let lit_rows = (0..grid.height)
+        .filter(|&y| (0..grid.width).any(|x| grid[Vec2::new(x as isize, y as isize)]))
+        .collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        Err("No text to recognize")?
+    };
+
+    let glyphs = match bottom - top + 1 {
+        6 => font_columns(&SMALL_FONT),
+        10 => font_columns(&LARGE_FONT),
+        height => Err(format!("No font with a height of {} pixels", height))?,
+    };
+    // Every column of the text as a bitmask of its lit rows.
+    let columns = (0..grid.width)
+        .map(|x| {
+            (top..=bottom).fold(0u16, |mask, y| {
+                mask << 1 | grid[Vec2::new(x as isize, y as isize)] as u16
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut text = String::new();
+    let mut unrecognized = Vec::new();
+    let mut x = 0;
+    while x < columns.len() {
+        if columns[x] == 0 {
+            x += 1;
+            continue;
+        }
+        // Letters are usually separated by blank columns, but wide letters may
+        // touch their neighbor, so try to match the longest glyph first.
+        let matched = glyphs
+            .iter()
+            .filter(|(_, glyph)| columns[x..].starts_with(glyph))
+            .max_by_key(|(_, glyph)| glyph.len());
+        if let Some((c, glyph)) = matched {
+            text.push(*c);
+            x += glyph.len();
+        } else {
+            let end = (x..columns.len())
+                .find(|&end| columns[end] == 0)
+                .unwrap_or(columns.len());
+            unrecognized.push(format!("{}..{}", x, end));
+            x = end;
+        }
+    }
+
+    if !unrecognized.is_empty() {
+        Err(format!(
+            "Unrecognized glyphs in columns {} (read {:?})",
+            unrecognized.join(", "),
+            text
+        ))?;
+    }
+    Ok(text)
+}
+
+/// Read the capital letters drawn by a row-major buffer of pixels, see
+/// [`ocr`].
+pub fn ocr_buffer(pixels: &[bool], width: usize) -> Result<String> {
+    if width == 0 || !pixels.len().is_multiple_of(width) {
+        Err(format!(
+            "A buffer of {} pixels does not have a width of {}",
+            pixels.len(),
+            width
+        ))?;
+    }
+    ocr(&Grid::from_data(width, pixels.len() / width, pixels.into()))
+}
+
+/// Read the capital letters drawn with `#` (or `█`) on a background of any
+/// other character, see [`ocr`].
+pub fn ocr_str(text: &str) -> Result<String> {
+    let rows = text
+        .lines()
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut grid = Grid::new(width, rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid[Vec2::new(x as isize, y as isize)] = c == '#' || c == '█';
+        }
+    }
+    ocr(&grid)
+}
+
+/// The columns of every glyph of a font as bitmasks of their lit rows.
+fn font_columns<const H: usize>(font: &[(char, [&str; H])]) -> Vec<(char, Vec<u16>)> {
+    font.iter()
+        .map(|(c, rows)| {
+            let columns = (0..rows[0].len())
+                .map(|x| {
+                    rows.iter().fold(0, |mask, row| {
+                        mask << 1 | (row.as_bytes()[x] == b'#') as u16
+                    })
+                })
+                .collect();
+            (*c, columns)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Itertools;
+
+    /// Draw `text` in `font`, with `spacing` blank columns between letters.
+    fn draw<const H: usize>(font: &[(char, [&str; H])], text: &str, spacing: usize) -> String {
+        let glyphs = text
+            .chars()
+            .map(|c| font.iter().find(|&&(l, _)| l == c).unwrap().1)
+            .collect::<Vec<_>>();
+        (0..H)
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .join(&".".repeat(spacing))
+                    + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_font() {
+        assert_eq!(ocr_str(&draw(&SMALL_FONT, "HIZY", 1)).unwrap(), "HIZY");
+        assert_eq!(ocr_str(&draw(&SMALL_FONT, "LYAK", 0)).unwrap(), "LYAK");
+    }
+
+    #[test]
+    fn test_large_font() {
+        let text = draw(&LARGE_FONT, "NXGJ", 2);
+        let pixels = text.lines().flat_map(|row| row.chars().map(|c| c == '#'));
+        assert_eq!(ocr_buffer(&pixels.collect::<Vec<_>>(), 30).unwrap(), "NXGJ");
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        let text = "
+.##...#.
+#..#..#.
+#..#..#.
+####.##.
+#..#.#..
+#..#.#..
+";
+        let Err(crate::AoCError::Message(error)) = ocr_str(text) else {
+            panic!("expected an error");
+        };
+        assert_eq!(error, "Unrecognized glyphs in columns 5..7 (read \"A\")");
+    }
+}
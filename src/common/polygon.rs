@@ -0,0 +1,207 @@
+use num_integer::Integer;
+use num_traits::Signed;
+
+use super::{AxisBox, Direction, Vec2};
+
+/// A simple polygon with integer vertices, given in order around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<Vec2<T>>,
+}
+
+impl<T: Integer + Signed + Copy> Polygon<T> {
+    pub fn new(vertices: Vec<Vec2<T>>) -> Self {
+        Self { vertices }
+    }
+
+    /// The polygon traced by moving `len` steps in each direction in turn,
+    /// starting at `start`. The moves should end where they started.
+    pub fn from_moves<I>(start: Vec2<T>, moves: I) -> Self
+    where
+        I: IntoIterator<Item = (Direction, T)>,
+    {
+        let mut pos = start;
+        let mut vertices = vec![start];
+        for (dir, len) in moves {
+            let step: Vec2<isize> = dir.into();
+            pos = pos
+                + step.map(|a| match a.signum() {
+                    1 => len,
+                    -1 => -len,
+                    _ => T::zero(),
+                });
+            vertices.push(pos);
+        }
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Self::new(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Vec2<T>] {
+        &self.vertices
+    }
+
+    /// The edges as pairs of consecutive vertices, including the closing one.
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2<T>, Vec2<T>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the signed area by the shoelace formula, which is positive if
+    /// the vertices go clockwise (with y pointing down).
+    pub fn signed_area2(&self) -> T {
+        self.edges()
+            .fold(T::zero(), |area, (a, b)| area + a.cross(b))
+    }
+
+    /// The area, rounded down if the polygon is not rectilinear.
+    pub fn area(&self) -> T {
+        self.signed_area2().abs() / (T::one() + T::one())
+    }
+
+    /// The number of lattice points on the edges.
+    pub fn boundary_count(&self) -> T {
+        self.edges().fold(T::zero(), |count, (a, b)| {
+            let d = (b - a).abs();
+            count + d.x.gcd(&d.y)
+        })
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem.
+    pub fn interior_count(&self) -> T {
+        let two = T::one() + T::one();
+        (self.signed_area2().abs() - self.boundary_count() + two) / two
+    }
+
+    /// Whether `pos` lies inside or on the edges of the polygon.
+    pub fn contains(&self, pos: Vec2<T>) -> bool {
+        self.contains_doubled(pos + pos)
+    }
+
+    /// Like [`Polygon::contains`], but for a point with its coordinates
+    /// doubled, so that points halfway between lattice points can be tested.
+    fn contains_doubled(&self, p: Vec2<T>) -> bool {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            let (a, b) = (a + a, b + b);
+            let cross = (b - a).cross(p - a);
+            let between = (p - a) * (p - b);
+            if cross.is_zero() && between.x <= T::zero() && between.y <= T::zero() {
+                return true;
+            }
+            // Count the edges crossing the ray from `p` towards positive x.
+            if (a.y > p.y) != (b.y > p.y) && (cross > T::zero()) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Whether every point of `rect`, from its minimum up to its inclusive
+    /// maximum, lies inside or on the edges of the polygon. The polygon must
+    /// be rectilinear.
+    pub fn contains_rect(&self, rect: &AxisBox<T, 2>) -> bool {
+        let (min, max) = (rect.min(), rect.max_inclusive());
+        if min.x < max.x && min.y < max.y {
+            // No edge may pass through the inside of the rectangle, which then
+            // lies either entirely inside or entirely outside the polygon.
+            let crossed = self.edges().any(|(a, b)| {
+                a.x.max(b.x) > min.x
+                    && a.x.min(b.x) < max.x
+                    && a.y.max(b.y) > min.y
+                    && a.y.min(b.y) < max.y
+            });
+            return !crossed && self.contains_doubled(min + max);
+        }
+
+        // The rectangle is a line segment or a point. The edges split it into
+        // pieces that lie entirely inside or outside, so test the ends and the
+        // middle of every piece.
+        let axis = if min.x < max.x { 0 } else { 1 };
+        let mut cuts = vec![min[axis], max[axis]];
+        for (a, b) in self.edges() {
+            let lo = a.zip_map(b, T::min);
+            let hi = a.zip_map(b, T::max);
+            let other = 1 - axis;
+            if lo[other] <= min[other] && min[other] <= hi[other] {
+                for c in [lo[axis], hi[axis]] {
+                    if min[axis] < c && c < max[axis] {
+                        cuts.push(c);
+                    }
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut p = min + min;
+        cuts.iter().all(|&c| {
+            p[axis] = c + c;
+            self.contains_doubled(p)
+        }) && cuts.windows(2).all(|w| {
+            p[axis] = w[0] + w[1];
+            self.contains_doubled(p)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Rect;
+
+    /// A U shape, open towards the top.
+    fn u_shape() -> Polygon<i64> {
+        use Direction::*;
+        Polygon::from_moves(
+            Vec2::new(0, 0),
+            [
+                (East, 2),
+                (South, 4),
+                (East, 2),
+                (North, 4),
+                (East, 2),
+                (South, 6),
+                (West, 6),
+                (North, 6),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_area() {
+        let polygon = u_shape();
+        assert_eq!(polygon.vertices().len(), 8);
+        assert_eq!(polygon.signed_area2(), 56);
+        assert_eq!(polygon.area(), 28);
+        assert_eq!(polygon.boundary_count(), 32);
+        assert_eq!(polygon.interior_count(), 13);
+
+        let triangle = Polygon::new(vec![Vec2::new(0, 0), Vec2::new(0, 4), Vec2::new(4, 0)]);
+        assert_eq!(triangle.signed_area2(), -16);
+        assert_eq!(triangle.boundary_count(), 12);
+        assert_eq!(triangle.interior_count(), 3);
+        assert!(triangle.contains(Vec2::new(2, 2)));
+        assert!(triangle.contains(Vec2::new(1, 1)));
+        assert!(!triangle.contains(Vec2::new(3, 2)));
+    }
+
+    #[test]
+    fn test_contains() {
+        let polygon = u_shape();
+        let inside = [(0, 0), (1, 3), (2, 2), (3, 4), (6, 6), (5, 1)];
+        let outside = [(3, 3), (-1, 0), (7, 2), (3, 0), (4, 7)];
+        assert!(inside.iter().all(|&p| polygon.contains(p.into())));
+        assert!(!outside.iter().any(|&p| polygon.contains(p.into())));
+
+        let rect = |a: (i64, i64), b: (i64, i64)| Rect::from_corners(a.into(), b.into());
+        assert!(polygon.contains_rect(&rect((0, 4), (6, 6))));
+        assert!(polygon.contains_rect(&rect((0, 0), (2, 6))));
+        assert!(!polygon.contains_rect(&rect((0, 0), (6, 6))));
+        assert!(!polygon.contains_rect(&rect((1, 3), (3, 5))));
+        // Segments along the edges, and across the opening of the U.
+        assert!(polygon.contains_rect(&rect((2, 0), (2, 4))));
+        assert!(polygon.contains_rect(&rect((0, 4), (6, 4))));
+        assert!(!polygon.contains_rect(&rect((0, 0), (6, 0))));
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt::{Debug, Display};
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Index, IndexMut};
+
+use foldhash::fast::FixedState;
+use hashbrown::HashMap;
+
+use super::grid::DisplayFn;
+use super::{Direction, Grid, Vec2};
+
+/// A grid without fixed bounds. Only cells that were set are stored, all
+/// other cells hold the default value.
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2<isize>, T>,
+    default: T,
+    bounds: Option<(Vec2<isize>, Vec2<isize>)>,
+    display_fn: Option<DisplayFn<T>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+            display_fn: None,
+        }
+    }
+
+    /// Build a sparse grid from a dense one with its cell `(0, 0)` at
+    /// `origin`, keeping only the cells that differ from `default`. The
+    /// bounds cover the whole dense grid.
+    pub fn from_grid(grid: Grid<T>, origin: Vec2<isize>, default: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        let (width, height) = (grid.width, grid.height);
+        if width > 0 && height > 0 {
+            sparse.grow(origin);
+            sparse.grow(origin + Vec2::new(width as isize - 1, height as isize - 1));
+        }
+        for (i, value) in grid.into_iter().enumerate() {
+            if value != sparse.default {
+                let pos = origin + Vec2::new((i % width) as isize, (i / width) as isize);
+                sparse.set(pos, value);
+            }
+        }
+        sparse
+    }
+
+    /// Copy the bounding box into a dense grid, together with the position of
+    /// its cell `(0, 0)`, which is the minimum of [`SparseGrid::bounds`].
+    pub fn to_grid(&self) -> (Vec2<isize>, Grid<T>)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds else {
+            return (Vec2::new(0, 0), Grid::from_data(0, 0, Box::new([])));
+        };
+        let data = self.coordinates().map(|c| self[c].clone()).collect();
+        let grid = Grid::from_data(
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            data,
+        );
+        (min, grid)
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Inclusive minimum and maximum of all positions that were ever set.
+    pub fn bounds(&self) -> Option<(Vec2<isize>, Vec2<isize>)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether a value was set at `pos`.
+    pub fn contains<C: Into<Vec2<isize>>>(&self, pos: C) -> bool {
+        self.cells.contains_key(&pos.into())
+    }
+
+    /// All positions in the bounding box, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        let (min, max) = self.bounds.unwrap_or((Vec2::new(0, 0), Vec2::new(-1, -1)));
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vec2::new(x, y)))
+    }
+
+    /// All positions that were set, together with their values.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2<isize>, &T)> + '_ {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    pub fn get<C: Into<Vec2<isize>>>(&self, pos: C) -> &T {
+        self.cells.get(&pos.into()).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut<C: Into<Vec2<isize>>>(&mut self, pos: C) -> &mut T
+    where
+        T: Clone,
+    {
+        let pos = pos.into();
+        self.grow(pos);
+        self.cells
+            .entry(pos)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    pub fn set<C: Into<Vec2<isize>>>(&mut self, pos: C, value: T) {
+        let pos = pos.into();
+        self.grow(pos);
+        self.cells.insert(pos, value);
+    }
+
+    /// Reset `pos` to the default value. The bounding box is not shrunk.
+    pub fn remove<C: Into<Vec2<isize>>>(&mut self, pos: C) -> Option<T> {
+        self.cells.remove(&pos.into())
+    }
+
+    fn grow(&mut self, pos: Vec2<isize>) {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((min, max)) => (
+                Vec2::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Vec2::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+        });
+    }
+
+    /// Map every set cell with `f`, using `default` for all other cells.
+    pub fn map<U, F: FnMut(Vec2<isize>, &T) -> U>(&self, default: U, mut f: F) -> SparseGrid<U> {
+        SparseGrid {
+            cells: self
+                .iter()
+                .map(|(pos, value)| (pos, f(pos, value)))
+                .collect(),
+            default,
+            bounds: self.bounds,
+            display_fn: None,
+        }
+    }
+
+    pub fn find(&self, value: &T) -> Option<Vec2<isize>>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|&(_, v)| v == value).map(|(pos, _)| pos)
+    }
+
+    /// All cardinal neighbors of `pos`.
+    pub fn cardinal_neighbors<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = Vec2<isize>> {
+        let pos = pos.into();
+        Direction::cardinal().map(move |dir| pos + dir)
+    }
+
+    /// All cardinal and ordinal neighbors of `pos`.
+    pub fn all_neighbors<C: Into<Vec2<isize>>>(&self, pos: C) -> impl Iterator<Item = Vec2<isize>> {
+        let pos = pos.into();
+        Direction::all().map(move |dir| pos + dir)
+    }
+
+    /// All cardinal neighbors of `pos`, together with their values.
+    pub fn cardinal_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &T)> + '_ {
+        self.cardinal_neighbors(pos.into())
+            .map(|p| (p, self.get(p)))
+    }
+
+    /// All cardinal and ordinal neighbors of `pos`, together with their
+    /// values.
+    pub fn all_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &T)> + '_ {
+        self.all_neighbors(pos.into()).map(|p| (p, self.get(p)))
+    }
+
+    pub fn with_display_fn<F: Fn(Vec2<isize>, &T) -> String + 'static>(self, f: F) -> Self {
+        Self {
+            display_fn: Some(Box::new(f)),
+            ..self
+        }
+    }
+}
+
+impl<T: Default + PartialEq> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid(grid, Vec2::new(0, 0), T::default())
+    }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Index<Vec2<isize>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2<isize>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T: Clone> IndexMut<Vec2<isize>> for SparseGrid<T> {
+    fn index_mut(&mut self, index: Vec2<isize>) -> &mut Self::Output {
+        self.get_mut(index)
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let pos = Vec2::new(x, y);
+                if let Some(display_fn) = &self.display_fn {
+                    write!(f, "{}", display_fn(pos, &self[pos]))?;
+                } else {
+                    write!(f, "{}", self[pos])?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{:?}", self[Vec2::new(x, y)])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Grids are equal if they hold the same value everywhere, regardless of
+/// which cells happen to store the default value explicitly.
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default
+            && self.iter().all(|(pos, value)| value == other.get(pos))
+            && other.iter().all(|(pos, value)| value == self.get(pos))
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T: Hash + PartialEq> SparseGrid<T> {
+    /// A cheap 64-bit fingerprint of the cells that differ from the default,
+    /// see [`Grid::fingerprint`].
+    pub fn fingerprint(&self) -> u64 {
+        // Cells are stored in no particular order, so combine their hashes
+        // with an order independent sum.
+        let cells = self.iter().filter(|&(_, value)| value != &self.default);
+        cells.fold(0u64, |sum, cell| {
+            sum.wrapping_add(FixedState::default().hash_one(cell))
+        })
+    }
+}
+
+impl<T: Clone> Clone for SparseGrid<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cells: self.cells.clone(),
+            default: self.default.clone(),
+            bounds: self.bounds,
+            display_fn: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        grid.set(Vec2::new(-2, 1), '#');
+        grid.set(Vec2::new(1, -1), '#');
+        assert_eq!(grid.bounds(), Some((Vec2::new(-2, -1), Vec2::new(1, 1))));
+        assert_eq!(grid[Vec2::new(0, 0)], '.');
+        assert_eq!(grid.to_string(), "...#\n....\n#...\n");
+    }
+
+    #[test]
+    fn test_dense_roundtrip() {
+        let mut grid = SparseGrid::new(0u8);
+        grid.set(Vec2::new(3, 4), 1);
+        grid.set(Vec2::new(5, 4), 2);
+        let (origin, dense) = grid.to_grid();
+        assert_eq!(origin, Vec2::new(3, 4));
+        assert_eq!((dense.width, dense.height), (3, 1));
+        let sparse = SparseGrid::from(dense);
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse[Vec2::new(2, 0)], 2);
+
+        // Default cells at the edges still count towards the bounds.
+        let dense = Grid::from_data(3, 2, Box::new([0, 1, 0, 0, 0, 0]));
+        let sparse = SparseGrid::from_grid(dense.clone(), Vec2::new(-2, 5), 0);
+        assert_eq!(sparse.len(), 1);
+        assert_eq!(sparse[Vec2::new(-1, 5)], 1);
+        assert_eq!(sparse.bounds(), Some((Vec2::new(-2, 5), Vec2::new(0, 6))));
+        assert_eq!(sparse.to_grid(), (Vec2::new(-2, 5), dense));
+    }
+}
@@ -1,417 +1,521 @@
-pub struct Vec2<T> {
-    pub x: T,
-    pub y: T,
-}
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+use num_integer::Integer;
+use num_traits::Signed;
+
+/// A vector with `N` components. The components of small vectors can also be
+/// accessed by name, as in `v.x`, `v.y`, `v.z` and `v.w`.
+#[repr(transparent)]
+pub struct VecN<T, const N: usize>(pub [T; N]);
+
+pub type Vec2<T> = VecN<T, 2>;
+pub type Vec3<T> = VecN<T, 3>;
+pub type Vec4<T> = VecN<T, 4>;
 
 impl<T> Vec2<T> {
     pub const fn new(x: T, y: T) -> Self {
-        Self { x, y }
-    }
-
-    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Vec2<U> {
-        Vec2 {
-            x: f(self.x),
-            y: f(self.y),
-        }
+        Self([x, y])
     }
+}
 
-    pub fn sum(self) -> T
-    where
-        T: std::ops::Add<Output = T>,
-    {
-        self.x + self.y
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self([x, y, z])
     }
 
-    pub fn len2(&self) -> T
+    pub fn xy(&self) -> Vec2<T>
     where
-        T: std::ops::Add<Output = T>,
-        T: std::ops::Mul<Output = T>,
-        T: Clone,
+        T: Copy,
     {
-        self.x.clone() * self.x.clone() + self.y.clone() * self.y.clone()
+        Vec2::new(self.x, self.y)
     }
 }
 
-impl Vec2<bool> {
-    pub fn all(self) -> bool {
-        self.x && self.y
+impl<T> Vec4<T> {
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self([x, y, z, w])
     }
 
-    pub fn any(self) -> bool {
-        self.x || self.y
+    pub fn xy(&self) -> Vec2<T>
+    where
+        T: Copy,
+    {
+        Vec2::new(self.x, self.y)
     }
-}
 
-impl<T: Clone> Clone for Vec2<T> {
-    fn clone(&self) -> Self {
-        Self {
-            x: self.x.clone(),
-            y: self.y.clone(),
-        }
+    pub fn xyz(&self) -> Vec3<T>
+    where
+        T: Copy,
+    {
+        Vec3::new(self.x, self.y, self.z)
     }
 }
 
-impl<T: Copy> Copy for Vec2<T> {}
-
-impl<T: Default> Default for Vec2<T> {
-    fn default() -> Self {
-        Self {
-            x: Default::default(),
-            y: Default::default(),
-        }
+impl<T, const N: usize> VecN<T, N> {
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> VecN<U, N> {
+        VecN(self.0.map(f))
     }
-}
-
-impl<T: std::ops::Add<Output = T>> std::ops::Add for Vec2<T> {
-    type Output = Self;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Vec2 {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+    /// Combine the components of two vectors pairwise with `f`.
+    pub fn zip_map<U, V, F: FnMut(T, U) -> V>(self, other: VecN<U, N>, mut f: F) -> VecN<V, N> {
+        let mut other = other.0.into_iter();
+        VecN(self.0.map(|a| f(a, other.next().unwrap())))
     }
-}
 
-impl<T: std::ops::AddAssign> std::ops::AddAssign for Vec2<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
     }
-}
 
-impl<T: std::ops::Sub<Output = T>> std::ops::Sub for Vec2<T> {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vec2 {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+    pub fn sum(self) -> T
+    where
+        T: std::ops::Add<Output = T>,
+    {
+        self.0
+            .into_iter()
+            .reduce(|a, b| a + b)
+            .expect("vector without components")
     }
-}
 
-impl<T: std::ops::SubAssign> std::ops::SubAssign for Vec2<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+    pub fn len2(&self) -> T
+    where
+        T: std::ops::Add<Output = T>,
+        T: std::ops::Mul<Output = T>,
+        T: Clone,
+    {
+        self.clone().zip_map(self.clone(), |a, b| a * b).sum()
     }
 }
 
-impl<T: std::ops::Mul<Output = T> + Copy> std::ops::Mul<T> for Vec2<T> {
-    type Output = Self;
+impl<T: Copy, const N: usize> VecN<T, N> {
+    pub fn dot(self, other: Self) -> T
+    where
+        T: std::ops::Add<Output = T> + std::ops::Mul<Output = T>,
+    {
+        (self * other).sum()
+    }
 
-    fn mul(self, rhs: T) -> Self::Output {
-        Vec2 {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
+    pub fn abs(self) -> Self
+    where
+        T: Signed,
+    {
+        self.map(|a| a.abs())
     }
-}
 
-impl<T: std::ops::MulAssign + Copy> std::ops::MulAssign<T> for Vec2<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
+    pub fn signum(self) -> Self
+    where
+        T: Signed,
+    {
+        self.map(|a| a.signum())
     }
-}
 
-impl<T: std::ops::Div<Output = T> + Copy> std::ops::Div<T> for Vec2<T> {
-    type Output = Self;
+    /// Sum of the absolute differences of the components.
+    pub fn manhattan(self, other: Self) -> T
+    where
+        T: Signed,
+    {
+        (self - other).abs().sum()
+    }
 
-    fn div(self, rhs: T) -> Self::Output {
-        Vec2 {
-            x: self.x / rhs,
-            y: self.y / rhs,
-        }
+    /// Largest absolute difference of the components.
+    pub fn chebyshev(self, other: Self) -> T
+    where
+        T: Signed + Ord,
+    {
+        (self - other)
+            .abs()
+            .0
+            .into_iter()
+            .max()
+            .expect("vector without components")
     }
-}
 
-impl<T: std::ops::DivAssign + Copy> std::ops::DivAssign<T> for Vec2<T> {
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
+    /// The lattice points on the line segment from `self` to `other`, both
+    /// included.
+    pub fn steps_to(self, other: Self) -> impl Iterator<Item = Self>
+    where
+        T: Integer + Signed,
+    {
+        let d = other - self;
+        let n = d.0.into_iter().fold(T::zero(), |n, a| n.gcd(&a));
+        let step = if n.is_zero() { d } else { d / n };
+        std::iter::successors(Some(self), move |&pos| (pos != other).then(|| pos + step))
+    }
+}
+
+impl<T: Copy + Signed> Vec2<T> {
+    /// The z component of the cross product, positive if `other` lies
+    /// clockwise of `self` (with y pointing down).
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Rotate by 90° clockwise, like [`super::Direction::right`].
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotate by 90° counterclockwise, like [`super::Direction::left`].
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T: Copy + Signed> Vec3<T> {
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// The vector under each of the 24 rotations that map axes onto axes.
+    /// The rotations come in the same order for every vector, so the n-th
+    /// items of two vectors are rotated the same way.
+    pub fn rotations(self) -> impl Iterator<Item = Self> {
+        // Permutations of the axes, with whether they are even.
+        const PERMUTATIONS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], true),
+            ([1, 2, 0], true),
+            ([2, 0, 1], true),
+            ([0, 2, 1], false),
+            ([2, 1, 0], false),
+            ([1, 0, 2], false),
+        ];
+        // A rotation negates an even number of axes for an even permutation
+        // and an odd number otherwise.
+        PERMUTATIONS.into_iter().flat_map(move |(axes, even)| {
+            (0..8u32)
+                .filter(move |negate| negate.count_ones().is_multiple_of(2) == even)
+                .map(move |negate| {
+                    VecN(std::array::from_fn(|i| {
+                        if negate >> i & 1 == 1 {
+                            -self[axes[i]]
+                        } else {
+                            self[axes[i]]
+                        }
+                    }))
+                })
+        })
     }
 }
 
-impl<T: std::ops::Neg<Output = T>> std::ops::Neg for Vec2<T> {
-    type Output = Self;
+impl<const N: usize> VecN<bool, N> {
+    pub fn all(self) -> bool {
+        self.0.into_iter().all(|b| b)
+    }
 
-    fn neg(self) -> Self::Output {
-        Vec2 {
-            x: -self.x,
-            y: -self.y,
-        }
+    pub fn any(self) -> bool {
+        self.0.into_iter().any(|b| b)
     }
 }
 
-impl<T: std::ops::Rem<Output = T>> std::ops::Rem for Vec2<T> {
-    type Output = Self;
-    fn rem(self, rhs: Self) -> Self::Output {
-        Vec2 {
-            x: self.x % rhs.x,
-            y: self.y % rhs.y,
-        }
-    }
+/// Named components of a [`Vec2`].
+#[repr(C)]
+pub struct XY<T> {
+    pub x: T,
+    pub y: T,
 }
 
-impl<T: std::cmp::PartialEq> std::cmp::PartialEq for Vec2<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
+/// Named components of a [`Vec3`].
+#[repr(C)]
+pub struct XYZ<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl<T: std::cmp::Eq> std::cmp::Eq for Vec2<T> {}
+/// Named components of a [`Vec4`].
+#[repr(C)]
+pub struct XYZW<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+macro_rules! impl_named_components {
+    ($n:literal, $named:ident) => {
+        impl<T> VecN<T, $n> {
+            /// Checked at compile time for every `T` the components are
+            /// accessed by name for.
+            const SAME_LAYOUT: () = {
+                assert!(size_of::<$named<T>>() == size_of::<[T; $n]>());
+                assert!(align_of::<$named<T>>() == align_of::<[T; $n]>());
+            };
+        }
+
+        impl<T> Deref for VecN<T, $n> {
+            type Target = $named<T>;
 
-impl<T: std::hash::Hash> std::hash::Hash for Vec2<T> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-    }
-}
+            fn deref(&self) -> &Self::Target {
+                // SAFETY: the named struct is `repr(C)` with `$n` fields of
+                // type `T`, so it has the same layout as `[T; $n]`, as
+                // asserted by `SAME_LAYOUT`.
+                let () = Self::SAME_LAYOUT;
+                unsafe { &*(self.0.as_ptr() as *const $named<T>) }
+            }
+        }
 
-impl<T: std::cmp::PartialOrd> std::cmp::PartialOrd for Vec2<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.x.partial_cmp(&other.x).and_then(|o| {
-            if o == std::cmp::Ordering::Equal {
-                self.y.partial_cmp(&other.y)
-            } else {
-                Some(o)
+        impl<T> DerefMut for VecN<T, $n> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                // SAFETY: see `deref`.
+                let () = Self::SAME_LAYOUT;
+                unsafe { &mut *(self.0.as_mut_ptr() as *mut $named<T>) }
             }
-        })
-    }
+        }
+    };
 }
 
-impl<T: std::cmp::Ord> std::cmp::Ord for Vec2<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.x.cmp(&other.x).then(self.y.cmp(&other.y))
-    }
-}
+impl_named_components!(2, XY);
+impl_named_components!(3, XYZ);
+impl_named_components!(4, XYZW);
 
-impl<T> From<(T, T)> for Vec2<T> {
-    fn from((x, y): (T, T)) -> Self {
-        Self { x, y }
+impl<T: Clone, const N: usize> Clone for VecN<T, N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
     }
 }
 
-impl<T> From<Vec2<T>> for (T, T) {
-    fn from(v: Vec2<T>) -> Self {
-        (v.x, v.y)
-    }
-}
+impl<T: Copy, const N: usize> Copy for VecN<T, N> {}
 
-impl<T: std::fmt::Debug> std::fmt::Debug for Vec2<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Vec2").field(&self.x).field(&self.y).finish()
+impl<T: Default, const N: usize> Default for VecN<T, N> {
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| T::default()))
     }
 }
 
-impl<T: std::fmt::Display> std::fmt::Display for Vec2<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("({}, {})", self.x, self.y))
-    }
-}
+impl<T, const N: usize> Index<usize> for VecN<T, N> {
+    type Output = T;
 
-pub struct Vec3<T> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
 }
 
-impl<T> Vec3<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
-        Self { x, y, z }
+impl<T, const N: usize> IndexMut<usize> for VecN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
     }
+}
 
-    pub fn xy(&self) -> Vec2<T>
-    where
-        T: Copy,
-    {
-        Vec2::new(self.x, self.y)
-    }
+/// Implement an operator component-wise between vectors, and between a
+/// vector and a scalar.
+macro_rules! impl_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl<T: std::ops::$op<Output = T>, const N: usize> std::ops::$op for VecN<T, N> {
+            type Output = Self;
 
-    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Vec3<U> {
-        Vec3 {
-            x: f(self.x),
-            y: f(self.y),
-            z: f(self.z),
+            fn $fn(self, rhs: Self) -> Self::Output {
+                self.zip_map(rhs, |a, b| a.$fn(b))
+            }
         }
-    }
 
-    pub fn sum(self) -> T
-    where
-        T: std::ops::Add<Output = T>,
-    {
-        self.x + self.y + self.z
-    }
+        impl<T: std::ops::$op<Output = T> + Copy, const N: usize> std::ops::$op<T> for VecN<T, N> {
+            type Output = Self;
 
-    pub fn len2(&self) -> T
-    where
-        T: std::ops::Add<Output = T>,
-        T: std::ops::Mul<Output = T>,
-        T: Clone,
-        T: std::fmt::Display,
-    {
-        self.x.clone() * self.x.clone()
-            + self.y.clone() * self.y.clone()
-            + self.z.clone() * self.z.clone()
-    }
-}
-
-impl Vec3<bool> {
-    pub fn all(self) -> bool {
-        self.x && self.y && self.z
-    }
+            fn $fn(self, rhs: T) -> Self::Output {
+                self.map(|a| a.$fn(rhs))
+            }
+        }
 
-    pub fn any(self) -> bool {
-        self.x || self.y || self.z
-    }
-}
+        impl<T: std::ops::$op_assign, const N: usize> std::ops::$op_assign for VecN<T, N> {
+            fn $fn_assign(&mut self, rhs: Self) {
+                for (a, b) in self.0.iter_mut().zip(rhs.0) {
+                    a.$fn_assign(b);
+                }
+            }
+        }
 
-impl<T: Clone> Clone for Vec3<T> {
-    fn clone(&self) -> Self {
-        Self {
-            x: self.x.clone(),
-            y: self.y.clone(),
-            z: self.z.clone(),
+        impl<T: std::ops::$op_assign + Copy, const N: usize> std::ops::$op_assign<T>
+            for VecN<T, N>
+        {
+            fn $fn_assign(&mut self, rhs: T) {
+                for a in self.0.iter_mut() {
+                    a.$fn_assign(rhs);
+                }
+            }
         }
-    }
+    };
 }
 
-impl<T: Copy> Copy for Vec3<T> {}
+impl_op!(Add, add, AddAssign, add_assign);
+impl_op!(Sub, sub, SubAssign, sub_assign);
+impl_op!(Mul, mul, MulAssign, mul_assign);
+impl_op!(Div, div, DivAssign, div_assign);
+impl_op!(Rem, rem, RemAssign, rem_assign);
 
-impl<T: std::ops::Add<Output = T>> std::ops::Add for Vec3<T> {
+impl<T: std::ops::Neg<Output = T>, const N: usize> std::ops::Neg for VecN<T, N> {
     type Output = Self;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Vec3 {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
     }
 }
 
-impl<T: std::ops::AddAssign> std::ops::AddAssign for Vec3<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
+impl<T: PartialEq, const N: usize> PartialEq for VecN<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
-impl<T: std::ops::Sub<Output = T>> std::ops::Sub for Vec3<T> {
-    type Output = Self;
+impl<T: Eq, const N: usize> Eq for VecN<T, N> {}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vec3 {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
+impl<T: std::hash::Hash, const N: usize> std::hash::Hash for VecN<T, N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
     }
 }
 
-impl<T: std::ops::SubAssign> std::ops::SubAssign for Vec3<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
+impl<T: PartialOrd, const N: usize> PartialOrd for VecN<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
     }
 }
 
-impl<T: std::ops::Mul<Output = T> + Copy> std::ops::Mul<T> for Vec3<T> {
-    type Output = Self;
-
-    fn mul(self, rhs: T) -> Self::Output {
-        Vec3 {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-        }
+impl<T: Ord, const N: usize> Ord for VecN<T, N> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
     }
 }
 
-impl<T: std::ops::MulAssign + Copy> std::ops::MulAssign<T> for Vec3<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
+impl<T, const N: usize> From<[T; N]> for VecN<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Self(components)
     }
 }
 
-impl<T: std::ops::Div<Output = T> + Copy> std::ops::Div<T> for Vec3<T> {
-    type Output = Self;
-
-    fn div(self, rhs: T) -> Self::Output {
-        Vec3 {
-            x: self.x / rhs,
-            y: self.y / rhs,
-            z: self.z / rhs,
-        }
+impl<T, const N: usize> From<VecN<T, N>> for [T; N] {
+    fn from(v: VecN<T, N>) -> Self {
+        v.0
     }
 }
 
-impl<T: std::ops::DivAssign + Copy> std::ops::DivAssign<T> for Vec3<T> {
-    fn div_assign(&mut self, rhs: T) {
-        self.x /= rhs;
-        self.y /= rhs;
-        self.z /= rhs;
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self([x, y])
     }
 }
 
-impl<T: std::ops::Neg<Output = T>> std::ops::Neg for Vec3<T> {
-    type Output = Self;
-
-    fn neg(self) -> Self::Output {
-        Vec3 {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z,
-        }
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(VecN([x, y]): Vec2<T>) -> Self {
+        (x, y)
     }
 }
 
-impl<T: std::cmp::PartialEq> std::cmp::PartialEq for Vec3<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self([x, y, z])
     }
 }
 
-impl<T: std::cmp::Eq> std::cmp::Eq for Vec3<T> {}
-
-impl<T: std::hash::Hash> std::hash::Hash for Vec3<T> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-        self.z.hash(state);
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(VecN([x, y, z]): Vec3<T>) -> Self {
+        (x, y, z)
     }
 }
 
-impl<T> From<(T, T, T)> for Vec3<T> {
-    fn from((x, y, z): (T, T, T)) -> Self {
-        Self { x, y, z }
+impl<T> From<(T, T, T, T)> for Vec4<T> {
+    fn from((x, y, z, w): (T, T, T, T)) -> Self {
+        Self([x, y, z, w])
     }
 }
 
-impl<T> From<Vec3<T>> for (T, T, T) {
-    fn from(v: Vec3<T>) -> Self {
-        (v.x, v.y, v.z)
+impl<T> From<Vec4<T>> for (T, T, T, T) {
+    fn from(VecN([x, y, z, w]): Vec4<T>) -> Self {
+        (x, y, z, w)
     }
 }
 
-impl<T: std::fmt::Debug> std::fmt::Debug for Vec3<T> {
+impl<T: std::fmt::Debug, const N: usize> std::fmt::Debug for VecN<T, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Vec3")
-            .field(&self.x)
-            .field(&self.y)
-            .field(&self.z)
-            .finish()
+        let mut tuple = f.debug_tuple(&format!("Vec{}", N));
+        for component in &self.0 {
+            tuple.field(component);
+        }
+        tuple.finish()
     }
 }
 
-impl<T: std::fmt::Display> std::fmt::Display for Vec3<T> {
+impl<T: std::fmt::Display, const N: usize> std::fmt::Display for VecN<T, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("({}, {}, {})", self.x, self.y, self.z))
+        write!(f, "(")?;
+        for (i, component) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", component)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_components() {
+        let mut v = Vec3::new(1, 2, 3);
+        v.z += 4;
+        assert_eq!((v.x, v.y, v.z), (1, 2, 7));
+        assert_eq!(v[2], 7);
+        assert_eq!(v.xy(), Vec2::new(1, 2));
+        let w = Vec4::new(1, 2, 3, 4);
+        assert_eq!(w.w, 4);
+        assert_eq!(w.xyz(), Vec3::new(1, 2, 3));
+        let mut u = Vec2::new((1u8, 2u16), (3, 4));
+        u.y.0 = 5;
+        assert_eq!(u.0, [(1, 2), (5, 4)]);
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = Vec2::new(7, -3);
+        let b = Vec2::new(2, 5);
+        assert_eq!(a + b, Vec2::new(9, 2));
+        assert_eq!(a - b, Vec2::new(5, -8));
+        assert_eq!(a * b, Vec2::new(14, -15));
+        assert_eq!(a * 2, Vec2::new(14, -6));
+        assert_eq!(a % 4, Vec2::new(3, -3));
+        assert_eq!(-a, Vec2::new(-7, 3));
+        assert_eq!(a.len2(), 58);
+        assert!(Vec3::new(1, 2, 3) < Vec3::new(1, 3, 0));
+        assert_eq!(Vec3::<i32>::default(), Vec3::new(0, 0, 0));
+        assert_eq!(format!("{} {:?}", a, b), "(7, -3) Vec2(2, 5)");
+    }
+
+    #[test]
+    fn test_metrics() {
+        let a = Vec2::new(7, -3);
+        let b = Vec2::new(2, 5);
+        assert_eq!(a.manhattan(b), 13);
+        assert_eq!(a.chebyshev(b), 8);
+        assert_eq!(a.dot(b), -1);
+        assert_eq!(a.cross(b), 41);
+        assert_eq!(a.signum(), Vec2::new(1, -1));
+        assert_eq!(Vec2::new(0, -1).rotate_right(), Vec2::new(1, 0));
+        assert_eq!(a.rotate_right().rotate_left(), a);
+        assert_eq!(
+            Vec2::new(1, 1)
+                .steps_to(Vec2::new(7, -3))
+                .collect::<Vec<_>>(),
+            [Vec2::new(1, 1), Vec2::new(4, -1), Vec2::new(7, -3)]
+        );
+        assert_eq!(a.steps_to(a).count(), 1);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let a = Vec3::new(1, 2, 3);
+        let b = Vec3::new(-4, 0, 5);
+        let mut rotated = a.rotations().collect::<Vec<_>>();
+        // Rotations preserve the cross product, unlike reflections.
+        for ((ra, rb), rc) in a.rotations().zip(b.rotations()).zip(a.cross(b).rotations()) {
+            assert_eq!(ra.cross(rb), rc);
+        }
+        rotated.sort();
+        rotated.dedup();
+        assert_eq!(rotated.len(), 24);
     }
 }
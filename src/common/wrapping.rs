@@ -0,0 +1,131 @@
+use std::ops::Index;
+
+use super::{Direction, Grid, Vec2};
+
+/// A view of a grid that repeats infinitely in every direction, so it can be
+/// indexed with any position. Position `(0, 0)` of tile `(0, 0)` is the top
+/// left of the underlying grid.
+pub struct WrappingView<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+    /// View the grid as an infinite repetition of itself.
+    pub fn wrapping(&self) -> WrappingView<'_, T> {
+        WrappingView { grid: self }
+    }
+
+    /// Wrap `pos` around the edges of the grid, as if it were a torus.
+    pub fn wrap<C: Into<Vec2<isize>>>(&self, pos: C) -> Vec2<isize> {
+        let pos = pos.into();
+        Vec2::new(
+            pos.x.rem_euclid(self.width as isize),
+            pos.y.rem_euclid(self.height as isize),
+        )
+    }
+
+    pub fn get_wrapping<C: Into<Vec2<isize>>>(&self, pos: C) -> &T {
+        &self[self.wrap(pos)]
+    }
+
+    pub fn get_wrapping_mut<C: Into<Vec2<isize>>>(&mut self, pos: C) -> &mut T {
+        let pos = self.wrap(pos);
+        &mut self[pos]
+    }
+}
+
+impl<'a, T> WrappingView<'a, T> {
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    /// The position within the underlying grid that `pos` maps to.
+    pub fn wrap<C: Into<Vec2<isize>>>(&self, pos: C) -> Vec2<isize> {
+        self.grid.wrap(pos)
+    }
+
+    /// The copy of the grid that `pos` falls in.
+    pub fn tile<C: Into<Vec2<isize>>>(&self, pos: C) -> Vec2<isize> {
+        let pos = pos.into();
+        Vec2::new(
+            pos.x.div_euclid(self.grid.width as isize),
+            pos.y.div_euclid(self.grid.height as isize),
+        )
+    }
+
+    /// Split `pos` into the tile it falls in and its position in that tile.
+    pub fn split<C: Into<Vec2<isize>>>(&self, pos: C) -> (Vec2<isize>, Vec2<isize>) {
+        let pos = pos.into();
+        (self.tile(pos), self.wrap(pos))
+    }
+
+    /// The position of `local` within `tile`.
+    pub fn join(&self, tile: Vec2<isize>, local: Vec2<isize>) -> Vec2<isize> {
+        Vec2::new(
+            tile.x * self.grid.width as isize + local.x,
+            tile.y * self.grid.height as isize + local.y,
+        )
+    }
+
+    pub fn get<C: Into<Vec2<isize>>>(&self, pos: C) -> &'a T {
+        self.grid.get_wrapping(pos)
+    }
+
+    /// All cardinal neighbors of `pos`, together with their values.
+    pub fn cardinal_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &'a T)> + '_ {
+        let pos = pos.into();
+        Direction::cardinal().map(move |dir| (pos + dir, self.get(pos + dir)))
+    }
+
+    /// All cardinal and ordinal neighbors of `pos`, together with their
+    /// values.
+    pub fn all_neighbor_values<C: Into<Vec2<isize>>>(
+        &self,
+        pos: C,
+    ) -> impl Iterator<Item = (Vec2<isize>, &'a T)> + '_ {
+        let pos = pos.into();
+        Direction::all().map(move |dir| (pos + dir, self.get(pos + dir)))
+    }
+}
+
+impl<T> Clone for WrappingView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WrappingView<'_, T> {}
+
+impl<T> Index<Vec2<isize>> for WrappingView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2<isize>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping() {
+        let grid = Grid::from_data(3, 2, (0..6).collect());
+        let view = grid.wrapping();
+        assert_eq!(view[Vec2::new(-1, 0)], 2);
+        assert_eq!(view[Vec2::new(4, -1)], 4);
+        assert_eq!(view.tile(Vec2::new(-1, 0)), Vec2::new(-1, 0));
+        assert_eq!(view.tile(Vec2::new(6, 5)), Vec2::new(2, 2));
+        assert_eq!(
+            view.split(Vec2::new(-4, 7)),
+            (Vec2::new(-2, 3), Vec2::new(2, 1))
+        );
+        assert_eq!(
+            view.join(Vec2::new(-2, 3), Vec2::new(2, 1)),
+            Vec2::new(-4, 7)
+        );
+    }
+}
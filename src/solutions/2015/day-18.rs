@@ -2,39 +2,23 @@ use aoc_rust::*;
 use common::*;
 
 struct Day18 {
-    grid: Grid<bool>,
-}
-
-impl Day18 {
-    fn step(&mut self) {
-        let mut new_grid = Grid::new(self.grid.height, self.grid.width);
-        for p in self.grid.coordinates() {
-            let neigbors = Direction::all()
-                .filter(|&d| self.grid.get(p + d) == Some(&true))
-                .count();
-            if self.grid[p] {
-                new_grid[p] = neigbors == 2 || neigbors == 3;
-            } else {
-                new_grid[p] = neigbors == 3;
-            }
-        }
-
-        self.grid = new_grid;
-    }
+    grid: BitGrid,
 }
 
 impl Problem<usize, usize> for Day18 {
     fn parse(input: &mut &str) -> PResult<Self> {
         Grid::parse(one_of(['.', '#']).map(|c| c == '#'))
-            .map(|grid| Self { grid })
+            .map(|grid| Self {
+                grid: BitGrid::from(&grid),
+            })
             .parse_next(input)
     }
 
     fn part1(mut self) -> Result<usize> {
         for _ in 0..100 {
-            self.step();
+            self.grid = self.grid.life_step();
         }
-        Ok(self.grid.coordinates().filter(|&c| self.grid[c]).count())
+        Ok(self.grid.count_ones())
     }
 
     fn part2(mut self) -> Result<usize> {
@@ -47,12 +31,12 @@ impl Problem<usize, usize> for Day18 {
             Vec2::new(n, m),
         ];
         for _ in 0..100 {
-            self.step();
+            self.grid = self.grid.life_step();
             for &corner in &corners {
-                self.grid[corner] = true;
+                self.grid.set(corner, true);
             }
         }
-        Ok(self.grid.coordinates().filter(|&c| self.grid[c]).count())
+        Ok(self.grid.count_ones())
     }
 }
 
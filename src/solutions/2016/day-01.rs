@@ -49,7 +49,7 @@ impl Problem<isize, isize> for Day01 {
             position += direction * instruction.distance as isize;
         }
 
-        Ok(position.x.abs() + position.y.abs())
+        Ok(position.abs().sum())
     }
 
     fn part2(self) -> Result<isize> {
@@ -67,7 +67,7 @@ impl Problem<isize, isize> for Day01 {
             for _ in 0..instruction.distance {
                 position += direction;
                 if !visited.insert(position) {
-                    return Ok(position.x.abs() + position.y.abs());
+                    return Ok(position.abs().sum());
                 }
             }
         }
@@ -5,80 +5,21 @@ struct Day08 {
     operations: Vec<Operation>,
 }
 
-struct Screen {
-    width: usize,
-    height: usize,
-    pixels: Vec<bool>,
-}
-
-impl Screen {
-    fn new(width: usize, height: usize) -> Self {
-        Self {
-            width,
-            height,
-            pixels: vec![false; width * height],
-        }
-    }
-
-    fn rect(&mut self, a: usize, b: usize) {
-        for y in 0..b {
-            for x in 0..a {
-                self.pixels[y * self.width + x] = true;
-            }
-        }
-    }
-
-    fn rotate_row(&mut self, row: usize, by: u32) {
-        let row = row * self.width;
-        let mut new_row = vec![false; self.width];
-        for x in 0..self.width {
-            new_row[(x + by as usize) % self.width] = self.pixels[row + x];
-        }
-        self.pixels.splice(row..row + self.width, new_row);
-    }
-
-    fn rotate_column(&mut self, column: usize, by: u32) {
-        let mut new_column = vec![false; self.height];
-        for y in 0..self.height {
-            new_column[(y + by as usize) % self.height] = self.pixels[y * self.width + column];
-        }
-        for (y, &pixel) in new_column.iter().enumerate() {
-            self.pixels[y * self.width + column] = pixel;
-        }
-    }
-
-    fn count_lit(&self) -> usize {
-        self.pixels.iter().filter(|&&x| x).count()
-    }
-
-    fn execute(&mut self, operations: &[Operation]) {
-        for operation in operations {
-            match operation {
-                Operation::Rect(a, b) => self.rect(*a, *b),
-                Operation::RotateRow { row, by } => self.rotate_row(*row, *by),
-                Operation::RotateColumn { column, by } => self.rotate_column(*column, *by),
-            }
-        }
-    }
-}
-
-impl std::fmt::Display for Screen {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                write!(
-                    f,
-                    "{}",
-                    if self.pixels[y * self.width + x] {
-                        '#'
-                    } else {
-                        '.'
-                    }
-                )?;
+impl Day08 {
+    fn screen(&self) -> BitGrid {
+        let mut screen = BitGrid::new(50, 6);
+        for operation in &self.operations {
+            match *operation {
+                Operation::Rect(a, b) => screen.fill_rect(
+                    Vec2::new(0, 0),
+                    Vec2::new(a as isize - 1, b as isize - 1),
+                    true,
+                ),
+                Operation::RotateRow { row, by } => screen.rotate_row(row, by as isize),
+                Operation::RotateColumn { column, by } => screen.rotate_column(column, by as isize),
             }
-            writeln!(f)?;
         }
-        Ok(())
+        screen
     }
 }
 
@@ -110,7 +51,7 @@ impl Operation {
     }
 }
 
-impl Problem<usize, ()> for Day08 {
+impl Problem<usize, String> for Day08 {
     fn parse(input: &mut &str) -> PResult<Self> {
         separated(0.., Operation::parse, line_ending)
             .map(|operations| Day08 { operations })
@@ -118,16 +59,11 @@ impl Problem<usize, ()> for Day08 {
     }
 
     fn part1(self) -> Result<usize> {
-        let mut screen = Screen::new(50, 6);
-        screen.execute(&self.operations);
-        Ok(screen.count_lit())
+        Ok(self.screen().count_ones())
     }
 
-    fn part2(self) -> Result<()> {
-        let mut screen = Screen::new(50, 6);
-        screen.execute(&self.operations);
-        println!("{}", screen);
-        Ok(())
+    fn part2(self) -> Result<String> {
+        ocr(&self.screen().to_grid())
     }
 }
 
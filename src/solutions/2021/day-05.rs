@@ -1,7 +1,6 @@
 use aoc_rust::*;
 use common::*;
 use hashbrown::HashSet;
-use num_integer::gcd;
 
 struct Line {
     start: Vec2<i32>,
@@ -26,10 +25,7 @@ impl Line {
     }
 
     fn points(&self) -> impl Iterator<Item = Vec2<i32>> + '_ {
-        let d = self.end - self.start;
-        let n = gcd(d.x.abs(), d.y.abs());
-        let d = d / n;
-        (0..=n).map(move |i| self.start + d * i)
+        self.start.steps_to(self.end)
     }
 }
 
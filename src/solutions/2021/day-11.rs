@@ -6,34 +6,23 @@ struct Day11 {
 }
 
 impl Day11 {
-    fn step(&mut self) -> usize {
-        let mut new = self.octopi.map(|_, &v| v + 1);
-        let mut flashes = 0;
-        fn flash(new: &mut Grid<u8>, pos: Vec2<isize>, flashes: &mut usize) {
-            new[pos] = 0;
-            *flashes += 1;
-            for d in Direction::all() {
-                let npos = pos + d;
-                if new.contains(npos) {
-                    if new[npos] == 0 {
-                        continue;
-                    }
-                    new[npos] += 1;
-                    if new[npos] > 9 {
-                        flash(new, npos, flashes);
-                    }
-                }
+    /// The number of flashes in every step.
+    fn flashes(self) -> impl Iterator<Item = usize> {
+        let mut octopi = Automaton::new(self.octopi, Neighborhood::Moore, |_, &energy, _| {
+            if energy > 9 {
+                0
+            } else {
+                energy
             }
-        }
-
-        for pos in self.octopi.coordinates() {
-            if new[pos] > 9 {
-                flash(&mut new, pos, &mut flashes);
-            }
-        }
-        self.octopi = new;
-
-        flashes
+        });
+        std::iter::repeat_with(move || {
+            let flashes = octopi.cascade(|_, energy| {
+                *energy += 1;
+                *energy == 10
+            });
+            octopi.step();
+            flashes
+        })
     }
 }
 
@@ -44,14 +33,17 @@ impl Problem<usize, usize> for Day11 {
             .parse_next(input)
     }
 
-    fn part1(mut self) -> Result<usize> {
-        Ok((0..100).map(|_| self.step()).sum())
+    fn part1(self) -> Result<usize> {
+        Ok(self.flashes().take(100).sum())
     }
 
-    fn part2(mut self) -> Result<usize> {
-        Ok((1..)
-            .find(|_| self.step() == self.octopi.width * self.octopi.height)
-            .unwrap())
+    fn part2(self) -> Result<usize> {
+        let octopi = self.octopi.width * self.octopi.height;
+        Ok(self
+            .flashes()
+            .position(|flashes| flashes == octopi)
+            .unwrap()
+            + 1)
     }
 }
 
@@ -11,56 +11,20 @@ struct Dig {
     len: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn parse(input: &mut &str) -> PResult<Self> {
-        alt((
-            "L".map(|_| Direction::Left),
-            "R".map(|_| Direction::Right),
-            "U".map(|_| Direction::Up),
-            "D".map(|_| Direction::Down),
-        ))
-        .parse_next(input)
-    }
-}
-
-impl std::ops::Add<Direction> for (isize, isize) {
-    type Output = (isize, isize);
-
-    fn add(self, rhs: Direction) -> Self::Output {
-        match rhs {
-            Direction::Left => (self.0 - 1, self.1),
-            Direction::Right => (self.0 + 1, self.1),
-            Direction::Up => (self.0, self.1 - 1),
-            Direction::Down => (self.0, self.1 + 1),
-        }
-    }
-}
-
-impl std::ops::Mul<usize> for Direction {
-    type Output = (isize, isize);
-
-    fn mul(self, rhs: usize) -> Self::Output {
-        match self {
-            Direction::Left => (-(rhs as isize), 0),
-            Direction::Right => (rhs as isize, 0),
-            Direction::Up => (0, -(rhs as isize)),
-            Direction::Down => (0, rhs as isize),
-        }
-    }
+fn parse_direction(input: &mut &str) -> PResult<Direction> {
+    alt((
+        "L".map(|_| Direction::West),
+        "R".map(|_| Direction::East),
+        "U".map(|_| Direction::North),
+        "D".map(|_| Direction::South),
+    ))
+    .parse_next(input)
 }
 
 impl Dig {
     fn parse(input: &mut &str, use_color: bool) -> PResult<Self> {
         separated_pair(
-            separated_pair(Direction::parse, space1, dec_usize),
+            separated_pair(parse_direction, space1, dec_usize),
             space1,
             delimited("(", preceded("#", hex_digit1.map(String::from)), ")"),
         )
@@ -71,10 +35,10 @@ impl Dig {
                 // len is the first 5 digits of the hex color
                 let len = usize::from_str_radix(&color[..5], 16).unwrap();
                 let dir = match &color.chars().nth(5).unwrap() {
-                    '0' => Direction::Right,
-                    '1' => Direction::Down,
-                    '2' => Direction::Left,
-                    '3' => Direction::Up,
+                    '0' => Direction::East,
+                    '1' => Direction::South,
+                    '2' => Direction::West,
+                    '3' => Direction::North,
                     _ => unreachable!(),
                 };
                 Dig { dir, len }
@@ -86,25 +50,12 @@ impl Dig {
 
 impl Day18 {
     fn calculate_area(&self) -> usize {
-        let mut pos: (isize, isize) = (0, 0);
-        let mut boundary = vec![pos];
-        let mut perimeter_len = 0;
-        for dig in &self.dig_plan {
-            pos.0 += (dig.dir * dig.len).0;
-            pos.1 += (dig.dir * dig.len).1;
-            boundary.push(pos);
-            perimeter_len += dig.len;
-        }
-        // use the shoelace formula to calculate the area
-        let area = boundary.windows(2).fold(0, |acc, pair| {
-            acc + pair[0].0 * pair[1].1 - pair[1].0 * pair[0].1
-        });
-
-        // pick's theorem:
-        // A = i + b/2 - 1 => i = A - b/2 + 1
-        // we add the perimeter length to the area because the perimeter is not included
-        // in the area so we get i = A + b/2 + 1
-        (area.unsigned_abs() / 2) + (perimeter_len / 2) + 1
+        let lagoon = Polygon::from_moves(
+            Vec2::new(0, 0),
+            self.dig_plan.iter().map(|dig| (dig.dir, dig.len as isize)),
+        );
+        // the trench itself is dug out too, so count the boundary as well
+        (lagoon.interior_count() + lagoon.boundary_count()) as usize
     }
 }
 
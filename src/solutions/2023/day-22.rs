@@ -2,63 +2,33 @@ use aoc_rust::*;
 use common::*;
 
 struct Day22 {
-    bricks: Vec<Brick>,
+    bricks: Vec<Cuboid<u32>>,
 }
 
-#[derive(Clone)]
-struct Brick {
-    x0: u32,
-    x1: u32,
-    y0: u32,
-    y1: u32,
-    z0: u32,
-    z1: u32,
-}
-
-impl Brick {
-    fn parse(input: &mut &str) -> PResult<Self> {
-        (
-            dec_u32, ",", dec_u32, ",", dec_u32, "~", dec_u32, ",", dec_u32, ",", dec_u32,
-        )
-            .map(|(x0, _, y0, _, z0, _, x1, _, y1, _, z1)| Brick {
-                x0,
-                x1,
-                y0,
-                y1,
-                z0,
-                z1,
-            })
+fn parse_brick(input: &mut &str) -> PResult<Cuboid<u32>> {
+    fn parse_vec3(input: &mut &str) -> PResult<Vec3<u32>> {
+        seq!(dec_u32, _: ',', dec_u32, _: ',', dec_u32)
+            .map(Vec3::from)
             .parse_next(input)
     }
-}
 
-impl std::fmt::Debug for Brick {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "[{}-{}; {}-{}; {}-{}]",
-            self.x0, self.x1, self.y0, self.y1, self.z0, self.z1
-        )
-    }
+    separated_pair(parse_vec3, '~', parse_vec3)
+        .map(|(a, b)| Cuboid::from_corners(a, b))
+        .parse_next(input)
 }
 
-fn drop_brick(brick: &Brick, tallest: &mut [[u32; 10]; 10]) -> Brick {
-    let mut brick = brick.clone();
-    let mut highest_brick = 0;
-    for x in brick.x0..=brick.x1 {
-        for y in brick.y0..=brick.y1 {
-            highest_brick = highest_brick.max(tallest[x as usize][y as usize]);
-        }
-    }
-    let dz = brick.z0 as i32 - highest_brick as i32 - 1;
-    let dz = dz.max(0) as u32;
-    brick.z0 -= dz;
-    brick.z1 -= dz;
-
-    for x in brick.x0..=brick.x1 {
-        for y in brick.y0..=brick.y1 {
-            tallest[x as usize][y as usize] = brick.z1;
-        }
+fn drop_brick(brick: &Cuboid<u32>, tallest: &mut [[u32; 10]; 10]) -> Cuboid<u32> {
+    let footprint = Rect::new(brick.min().xy(), brick.max().xy());
+    let highest_brick = footprint
+        .points()
+        .map(|p| tallest[p.x as usize][p.y as usize])
+        .max()
+        .unwrap_or(0);
+    let dz = Vec3::new(0, 0, brick.min().z.saturating_sub(highest_brick + 1));
+    let brick = Cuboid::new(brick.min() - dz, brick.max() - dz);
+
+    for p in footprint.points() {
+        tallest[p.x as usize][p.y as usize] = brick.max_inclusive().z;
     }
 
     brick
@@ -66,14 +36,14 @@ fn drop_brick(brick: &Brick, tallest: &mut [[u32; 10]; 10]) -> Brick {
 
 impl Problem<usize, usize> for Day22 {
     fn parse(input: &mut &str) -> PResult<Self> {
-        list(Brick::parse, line_ending)
+        list(parse_brick, line_ending)
             .map(|bricks| Self { bricks })
             .parse_next(input)
     }
 
     fn part1(self) -> Result<usize> {
         let mut bricks = self.bricks;
-        bricks.sort_by_key(|brick| brick.z0);
+        bricks.sort_by_key(|brick| brick.min().z);
 
         let mut tallest = [[0; 10]; 10];
         for b in &mut bricks {
@@ -88,7 +58,7 @@ impl Problem<usize, usize> for Day22 {
                 if i == j {
                     continue;
                 }
-                if b.z0 != drop_brick(b, &mut tallest).z0 {
+                if b.min().z != drop_brick(b, &mut tallest).min().z {
                     dropped = true;
                     break;
                 }
@@ -103,7 +73,7 @@ impl Problem<usize, usize> for Day22 {
 
     fn part2(self) -> Result<usize> {
         let mut bricks = self.bricks;
-        bricks.sort_by_key(|brick| brick.z0);
+        bricks.sort_by_key(|brick| brick.min().z);
 
         let mut tallest = [[0; 10]; 10];
         for b in &mut bricks {
@@ -117,7 +87,7 @@ impl Problem<usize, usize> for Day22 {
                 if i == j {
                     continue;
                 }
-                if b.z0 != drop_brick(b, &mut tallest).z0 {
+                if b.min().z != drop_brick(b, &mut tallest).min().z {
                     cnt += 1;
                 }
             }
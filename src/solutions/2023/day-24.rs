@@ -1,7 +1,8 @@
 use aoc_rust::*;
 use common::*;
-use ndarray::prelude::*;
-use ndarray_linalg::Solve;
+
+/// The rock's velocity is searched for in this range along each axis.
+const MAX_SPEED: i64 = 1000;
 
 struct Day24 {
     hailstones: Vec<HailStone>,
@@ -26,31 +27,14 @@ impl HailStone {
             .parse_next(input)
     }
 
-    fn intersects(&self, other: &Self) -> Option<(f64, f64)> {
-        let Vec2 { x: x1, y: y1 } = self.pos.xy();
-        let Vec2 { x: x2, y: y2 } = other.pos.xy();
-        let Vec2 { x: u1, y: v1 } = self.vel.xy();
-        let Vec2 { x: u2, y: v2 } = other.vel.xy();
-
-        // [ u1, -u2 ] [ a ] = [ x2 - x1 ]
-        // [ v1, -v2 ] [ b ] = [ y2 - y1 ]
-        // Ax = b
-        let a: Array2<f64> = array![[u1 as f64, -u2 as f64], [v1 as f64, -v2 as f64]];
-
-        let b: Array1<f64> = array![x2 as f64 - x1 as f64, y2 as f64 - y1 as f64];
-
-        let x = a.solve_into(b).ok()?;
-        let t = x[0];
-        let s = x[1];
-
-        if t < 0. || s < 0. {
-            return None;
-        }
-
-        let x = x1 as f64 + u1 as f64 * t;
-        let y = y1 as f64 + v1 as f64 * t;
+    /// The path of the hailstone, ignoring the z axis.
+    fn path(&self) -> Line {
+        Line::new(self.pos.xy(), self.vel.xy())
+    }
 
-        Some((x, y))
+    /// The path of the hailstone as seen from something moving at `vel`.
+    fn relative_path(&self, vel: Vec3<i64>) -> Line3 {
+        Line3::new(self.pos, self.vel - vel)
     }
 }
 
@@ -69,11 +53,11 @@ impl Problem<usize, i64> for Day24 {
 
     fn part1(self) -> Result<usize> {
         let mut cnt = 0;
-        let r = 200000000000000.0..=400000000000000.0;
+        let r = Ratio::from(200000000000000i64)..=Ratio::from(400000000000000i64);
         for (i, a) in self.hailstones.iter().enumerate() {
             for b in self.hailstones.iter().skip(i + 1) {
-                if let Some((x, y)) = a.intersects(b) {
-                    if r.contains(&x) && r.contains(&y) {
+                if let Some((pos, _, _)) = a.path().ray_intersection(&b.path()) {
+                    if r.contains(&pos.x) && r.contains(&pos.y) {
                         cnt += 1;
                     }
                 }
@@ -83,69 +67,83 @@ impl Problem<usize, i64> for Day24 {
     }
 
     fn part2(self) -> Result<i64> {
-        let HailStone { pos: p1, vel: v1 } = self.hailstones[0];
-        let HailStone { pos: p2, vel: v2 } = self.hailstones[1];
-        let HailStone { pos: p3, vel: v3 } = self.hailstones[2];
-
-        let Vec3 {
-            x: x1,
-            y: y1,
-            z: z1,
-        } = p1;
-        let Vec3 {
-            x: x2,
-            y: y2,
-            z: z2,
-        } = p2;
-        let Vec3 {
-            x: x3,
-            y: y3,
-            z: z3,
-        } = p3;
-
-        let Vec3 {
-            x: u1,
-            y: v1,
-            z: w1,
-        } = v1;
-        let Vec3 {
-            x: u2,
-            y: v2,
-            z: w2,
-        } = v2;
-        let Vec3 {
-            x: u3,
-            y: v3,
-            z: w3,
-        } = v3;
-
-        let a: Array2<i64> = array![
-            [0, -w1 + w3, v1 - v3, 0, z1 - z3, -y1 + y3],
-            [w1 - w3, 0, -u1 + u3, -z1 + z3, 0, x1 - x3],
-            [-v1 + v3, u1 - u3, 0, y1 - y3, -x1 + x3, 0],
-            [0, -w2 + w3, v2 - v3, 0, z2 - z3, -y2 + y3],
-            [w2 - w3, 0, -u2 + u3, -z2 + z3, 0, x2 - x3],
-            [-v2 + v3, u2 - u3, 0, y2 - y3, -x2 + x3, 0],
-        ];
-
-        let b: Array1<i64> = -array![
-            w1 * y1 - w3 * y3 - v1 * z1 + v3 * z3,
-            -w1 * x1 + w3 * x3 + u1 * z1 - u3 * z3,
-            v1 * x1 - v3 * x3 - u1 * y1 + u3 * y3,
-            w2 * y2 - w3 * y3 - v2 * z2 + v3 * z3,
-            -w2 * x2 + w3 * x3 + u2 * z2 - u3 * z3,
-            v2 * x2 - v3 * x3 - u2 * y2 + u3 * y3
-        ];
-
-        let a: Array2<f64> = a.map(|&x| x as f64);
-        let b: Array1<f64> = b.map(|&x| x as f64);
-
-        let x = a.solve(&b).unwrap();
-
-        Ok((x[0] + x[1] + x[2]).ceil() as i64)
+        // Seen from the rock, every hailstone passes through the rock's
+        // starting position. Find the velocity for which the paths of the
+        // first few hailstones meet, first in the xy plane and then in space.
+        let speeds = [0, 1, 2].map(|axis| self.possible_speeds(axis));
+        let stones = &self.hailstones[..self.hailstones.len().min(4)];
+        for &vx in &speeds[0] {
+            for &vy in &speeds[1] {
+                let paths = stones
+                    .iter()
+                    .map(|h| h.relative_path(Vec3::new(vx, vy, 0)))
+                    .map(|l| Line::new(l.point.xy(), l.dir.xy()))
+                    .collect::<Vec<_>>();
+                if meeting_point(&paths).is_none() {
+                    continue;
+                }
+                for &vz in &speeds[2] {
+                    let paths = stones
+                        .iter()
+                        .map(|h| h.relative_path(Vec3::new(vx, vy, vz)))
+                        .collect::<Vec<_>>();
+                    let Some(pos) = paths[0].intersect(&paths[1]) else {
+                        continue;
+                    };
+                    if paths[2..]
+                        .iter()
+                        .all(|l| l.intersect(&paths[0]) == Some(pos))
+                    {
+                        let sum = pos.x + pos.y + pos.z;
+                        return sum
+                            .to_integer()
+                            .map(|sum| sum as i64)
+                            .ok_or(AoCError::NoSolution);
+                    }
+                }
+            }
+        }
+        Err(AoCError::NoSolution)
     }
 }
 
+impl Day24 {
+    /// The speeds the rock may have along `axis`. Two hailstones with the
+    /// same speed along it stay the same distance apart, which the rock has
+    /// to cover between hitting them in a whole number of nanoseconds.
+    fn possible_speeds(&self, axis: usize) -> Vec<i64> {
+        let mut gaps = Vec::new();
+        for (i, a) in self.hailstones.iter().enumerate() {
+            for b in &self.hailstones[i + 1..] {
+                if a.vel[axis] == b.vel[axis] {
+                    gaps.push((a.vel[axis], b.pos[axis] - a.pos[axis]));
+                }
+            }
+        }
+        (-MAX_SPEED..=MAX_SPEED)
+            .filter(|&v| {
+                gaps.iter().all(|&(vel, gap)| match v - vel {
+                    0 => gap == 0,
+                    d => gap % d == 0,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The point where all `paths` cross, if they are all reached ahead.
+fn meeting_point(paths: &[Line]) -> Option<Vec2<Ratio>> {
+    let mut meet = None;
+    for other in &paths[1..] {
+        let (pos, _, _) = paths[0].ray_intersection(other)?;
+        if meet.is_some_and(|meet| meet != pos) {
+            return None;
+        }
+        meet = Some(pos);
+    }
+    meet
+}
+
 aoc_main!(Day24);
 
 #[cfg(test)]
@@ -162,4 +160,9 @@ mod tests {
     fn test_part1() {
         assert_task!(Day24, 1, EXAMPLE, 0);
     }
+
+    #[test]
+    fn test_part2() {
+        assert_task!(Day24, 2, EXAMPLE, 47);
+    }
 }
@@ -28,21 +28,15 @@ impl Problem<usize, usize> for Day04 {
     }
 
     fn part2(self) -> Result<usize> {
-        Ok(self
-            .grid
-            .coordinates()
-            .filter(|c| self.grid.get(*c) == Some(&b'A'))
-            .filter(|c| {
-                let corners = Direction::ordinal()
-                    .map(|d| self.grid.get(*c + d).unwrap_or(&b' '))
-                    .collect::<Vec<_>>();
-
-                let d1 = [*corners[0], *corners[2]];
-                let d2 = [*corners[1], *corners[3]];
-
-                (&d1 == b"MS" || &d1 == b"SM") && (&d2 == b"MS" || &d2 == b"SM")
-            })
-            .count())
+        let pattern = Grid::from_data(
+            3,
+            3,
+            "M.S.A.M.S"
+                .bytes()
+                .map(|c| (c != b'.').then_some(c))
+                .collect(),
+        );
+        Ok(self.grid.find_pattern_oriented(&pattern).len())
     }
 }
 
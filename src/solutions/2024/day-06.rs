@@ -1,6 +1,5 @@
 use aoc_rust::*;
 use common::*;
-use hashbrown::{HashMap, HashSet};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum Cell {
@@ -26,25 +25,27 @@ impl Cell {
 struct Day06 {
     start: Vec2<isize>,
     grid: Grid<Cell>,
-    jump_table: HashMap<(Vec2<isize>, Direction), Vec2<isize>>,
+    /// For every cell and cardinal direction, the index of the cell in
+    /// front of the next wall in that direction.
+    jump_table: Grid<[Option<u32>; 4]>,
 }
 
 impl Day06 {
     fn loops(&self, mut pos: Vec2<isize>, mut dir: Direction, stone: Vec2<isize>) -> bool {
-        let mut visited = HashSet::with_capacity(500);
+        let mut visited = Grid::<DirectionSet>::new(self.grid.width, self.grid.height);
         while self.grid.get(pos).is_some() {
-            if visited.contains(&(pos, dir)) {
+            if !visited[pos].insert(dir) {
                 return true;
             }
-            visited.insert((pos, dir));
             if pos.x == stone.x || pos.y == stone.y {
                 while let Some(&Cell::Wall) = self.grid.get(pos + dir) {
                     dir = dir.right();
                 }
                 pos += dir;
             } else {
-                if let Some(&jump) = self.jump_table.get(&(pos, dir)) {
-                    pos = jump;
+                if let Some(jump) = self.jump_table[pos][dir as usize] {
+                    let width = self.grid.width as u32;
+                    pos = Vec2::new((jump % width) as isize, (jump / width) as isize);
                 } else {
                     return false;
                 }
@@ -60,7 +61,7 @@ impl Problem<usize, usize> for Day06 {
     fn parse(input: &mut &str) -> PResult<Self> {
         Grid::parse(Cell::parse)
             .map(|grid| {
-                let mut jump_table = HashMap::new();
+                let mut jump_table = Grid::<[Option<u32>; 4]>::new(grid.width, grid.height);
                 let mut start = Default::default();
                 for pos in grid.coordinates() {
                     if grid.get(pos) == Some(&Cell::Start) {
@@ -70,7 +71,8 @@ impl Problem<usize, usize> for Day06 {
                         let mut jump = pos;
                         while let Some(c) = grid.get(jump + dir) {
                             if c == &Cell::Wall {
-                                jump_table.insert((pos, dir), jump);
+                                let index = jump.y as usize * grid.width + jump.x as usize;
+                                jump_table[pos][dir as usize] = Some(index as u32);
                                 break;
                             }
                             jump += dir;
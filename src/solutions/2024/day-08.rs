@@ -31,33 +31,23 @@ impl std::fmt::Debug for Cell {
 
 struct Day08 {
     grid: Grid<Cell>,
+    antennas: HashMap<char, Vec<Vec2<isize>>>,
 }
 
 impl Problem<usize, usize> for Day08 {
     fn parse(input: &mut &str) -> PResult<Self> {
-        Grid::parse(Cell::parse)
-            .map(|grid| Self { grid })
-            .parse_next(input)
+        Grid::parse_with_markers(Cell::parse.map(|cell| match cell {
+            Cell::Antenna(c) => (cell, Some(c)),
+            Cell::Empty => (cell, None),
+        }))
+        .map(|(grid, antennas)| Self { grid, antennas })
+        .parse_next(input)
     }
 
     fn part1(self) -> Result<usize> {
-        let antennas = self
-            .grid
-            .coordinates()
-            .filter_map(|pos| {
-                self.grid.get(pos).and_then(|cell| match cell {
-                    Cell::Antenna(c) => Some((*c, pos)),
-                    _ => None,
-                })
-            })
-            .fold(HashMap::new(), |mut antennas, (c, pos)| {
-                antennas.entry(c).or_insert_with(Vec::new).push(pos);
-                antennas
-            });
-
         let mut antinodes = HashSet::new();
 
-        for (_, positions) in antennas {
+        for positions in self.antennas.into_values() {
             for (a, b) in positions.into_iter().tuple_combinations() {
                 let d = b - a;
                 antinodes.insert(a - d);
@@ -72,23 +62,9 @@ impl Problem<usize, usize> for Day08 {
     }
 
     fn part2(self) -> Result<usize> {
-        let antennas = self
-            .grid
-            .coordinates()
-            .filter_map(|pos| {
-                self.grid.get(pos).and_then(|cell| match cell {
-                    Cell::Antenna(c) => Some((*c, pos)),
-                    _ => None,
-                })
-            })
-            .fold(HashMap::new(), |mut antennas, (c, pos)| {
-                antennas.entry(c).or_insert_with(Vec::new).push(pos);
-                antennas
-            });
-
         let mut antinodes = HashSet::new();
 
-        for (_, positions) in antennas {
+        for positions in self.antennas.into_values() {
             for (mut a, mut b) in positions.into_iter().tuple_combinations() {
                 let d = b - a;
                 while self.grid.contains(a) {
@@ -12,9 +12,8 @@ impl Day10 {
         }
 
         let mut v = Vec::new();
-        for d in Direction::cardinal() {
-            let new_pos = pos + d;
-            if self.grid.contains(new_pos) && self.grid[new_pos] == self.grid[pos] + 1 {
+        for new_pos in self.grid.cardinal_neighbors(pos) {
+            if self.grid[new_pos] == self.grid[pos] + 1 {
                 v.extend(self.solve(new_pos));
             }
         }
@@ -1,5 +1,3 @@
-use std::collections::VecDeque;
-
 use aoc_rust::*;
 use common::*;
 
@@ -7,36 +5,6 @@ struct Day12 {
     garden: Grid<u8>,
 }
 
-impl Day12 {
-    fn plots(&self) -> Vec<HashSet<Vec2<isize>>> {
-        let mut plots = Vec::new();
-        let mut visited = Grid::<bool>::new(self.garden.width, self.garden.height);
-
-        for pos in self.garden.coordinates() {
-            if visited[pos] {
-                continue;
-            }
-            let val = self.garden[pos];
-            let mut queue = VecDeque::new();
-            let mut plot = HashSet::new();
-            queue.push_back(pos);
-            while let Some(pos) = queue.pop_front() {
-                if !self.garden.contains(pos) || plot.contains(&pos) || self.garden[pos] != val {
-                    continue;
-                }
-                plot.insert(pos);
-                visited[pos] = true;
-                for dir in Direction::cardinal() {
-                    queue.push_back(pos + dir);
-                }
-            }
-
-            plots.push(plot);
-        }
-        plots
-    }
-}
-
 impl Problem<usize, usize> for Day12 {
     fn parse(input: &mut &str) -> PResult<Self> {
         Grid::parse(
@@ -50,41 +18,21 @@ impl Problem<usize, usize> for Day12 {
 
     fn part1(self) -> Result<usize> {
         Ok(self
-            .plots()
-            .into_iter()
-            .map(|plot| {
-                let area = plot.len();
-                let perimeter = plot
-                    .iter()
-                    .flat_map(|&pos| Direction::cardinal().map(move |dir| pos + dir))
-                    .filter(|&pos| !plot.contains(&pos))
-                    .count();
-
-                area * perimeter
-            })
+            .garden
+            .regions()
+            .regions
+            .iter()
+            .map(|plot| plot.area * plot.perimeter)
             .sum())
     }
 
     fn part2(self) -> Result<usize> {
         Ok(self
-            .plots()
-            .into_iter()
-            .map(|plot| {
-                let area = plot.len();
-                let sides: usize = Direction::cardinal()
-                    .map(|dir| {
-                        let side = plot
-                            .iter()
-                            .map(|&pos| pos + dir)
-                            .filter(|&pos| !plot.contains(&pos))
-                            .collect::<HashSet<_>>();
-                        let r = dir.right();
-                        let offset_side = side.iter().map(|&pos| pos + r).collect::<HashSet<_>>();
-                        (&side - &offset_side).len()
-                    })
-                    .sum();
-                area * sides
-            })
+            .garden
+            .regions()
+            .regions
+            .iter()
+            .map(|plot| plot.area * plot.sides)
             .sum())
     }
 }
@@ -1,5 +1,3 @@
-use std::collections::VecDeque;
-
 use aoc_rust::*;
 use common::*;
 
@@ -41,6 +39,18 @@ struct Day16 {
     maze: Grid<Cell>,
 }
 
+impl Day16 {
+    fn paths(&self, start: Vec2<isize>) -> ShortestPaths<(Vec2<isize>, Direction)> {
+        ShortestPaths::dijkstra([(start, Direction::East)], |&(pos, dir)| {
+            let mut successors = vec![((pos, dir.right()), 1000), ((pos, dir.left()), 1000)];
+            if self.maze.get(pos + dir).is_some_and(|&c| c != Cell::Wall) {
+                successors.push(((pos + dir, dir), 1));
+            }
+            successors
+        })
+    }
+}
+
 impl Problem<usize, usize> for Day16 {
     fn parse(input: &mut &str) -> PResult<Self> {
         Grid::parse(Cell::parse)
@@ -52,64 +62,21 @@ impl Problem<usize, usize> for Day16 {
         let start = self.maze.find(&Cell::Start).ok_or("Could not find start")?;
         let end = self.maze.find(&Cell::End).ok_or("Could not find end")?;
 
-        let s = move |&(pos, dir): &(Vec2<isize>, Direction)| {
-            let mut successors = vec![((pos, dir.right()), 1000), ((pos, dir.left()), 1000)];
-            if self.maze.contains(pos + dir) && self.maze[pos + dir] != Cell::Wall {
-                successors.push(((pos + dir, dir), 1));
-            }
-            successors
-        };
-
-        let (_, len) = dijkstra(&(start, Direction::East), s, |&(p, _)| p == end)
+        let paths = self.paths(start);
+        let (_, len) = paths
+            .closest(Direction::cardinal().map(|dir| (end, dir)))
             .ok_or("Could not find path")?;
 
-        Ok(len)
+        Ok(len as usize)
     }
 
     fn part2(self) -> Result<usize> {
         let start = self.maze.find(&Cell::Start).ok_or("Could not find start")?;
         let end = self.maze.find(&Cell::End).ok_or("Could not find end")?;
 
-        let s = move |&(pos, dir): &(Vec2<isize>, Direction)| {
-            let mut successors = vec![((pos, dir.right()), 1000), ((pos, dir.left()), 1000)];
-            if self.maze.contains(pos + dir) && self.maze[pos + dir] != Cell::Wall {
-                successors.push(((pos + dir, dir), 1));
-            }
-            successors
-        };
-
-        let map = dijkstra_all(&(start, Direction::East), s);
-        let end_state = Direction::cardinal()
-            .map(move |dir| (end, dir))
-            .min_by_key(|state| map.get(state).unwrap().1)
-            .unwrap();
-
-        let mut visited = HashSet::new();
-        let mut q = VecDeque::from([(end_state, map[&end_state].1)]);
-
-        while let Some(((pos, dir), dst)) = q.pop_front() {
-            if visited.contains(&(pos, dir)) {
-                continue;
-            }
-            visited.insert((pos, dir));
-            if let Some(&(_, prev)) = map.get(&(pos - dir, dir)) {
-                if prev + 1 == dst {
-                    q.push_back(((pos - dir, dir), prev));
-                }
-            }
-            if let Some(&(_, prev)) = map.get(&(pos, dir.right())) {
-                if prev + 1000 == dst {
-                    q.push_back(((pos, dir.right()), prev));
-                }
-            }
-            if let Some(&(_, prev)) = map.get(&(pos, dir.left())) {
-                if prev + 1000 == dst {
-                    q.push_back(((pos, dir.left()), prev));
-                }
-            }
-        }
-
-        Ok(visited
+        let paths = self.paths(start);
+        Ok(paths
+            .optimal_states(Direction::cardinal().map(|dir| (end, dir)))
             .into_iter()
             .map(|(pos, _)| pos)
             .collect::<HashSet<_>>()
@@ -30,20 +30,12 @@ impl Problem<usize, String> for Day18 {
             g.set(pos, true);
         }
 
-        let grid = g.clone();
-        let s = move |&pos: &Vec2<isize>| {
-            Direction::cardinal()
-                .map(|dir| pos + dir)
-                .filter(|&p| grid.contains(p) && !grid[p])
-                .collect::<Vec<_>>()
-        };
-
         let start = Vec2::new(0, 0);
         let end = Vec2::new(size as isize - 1, size as isize - 1);
 
-        let path = bfs(&start, s, |&p| p == end).ok_or("Could not find path")?;
+        let distances = g.bfs([start], |_, &corrupted| !corrupted);
 
-        Ok(path.len() - 1)
+        Ok(distances[end].ok_or("Could not find path")? as usize)
     }
 
     fn part2(self) -> Result<String> {
@@ -85,8 +85,7 @@ impl Day20 {
         let mut ans = 0;
         for (i, &p1) in path.iter().enumerate() {
             for &p2 in path.iter().skip(i + 1) {
-                let d = p2 - p1;
-                let d = (d.x.abs() + d.y.abs()) as usize;
+                let d = p1.manhattan(p2) as usize;
                 if d <= max_cheat_time {
                     let total_dist = start_dst[p1] + end_dst[p2] + d;
                     if (dist as isize - total_dist as isize) >= 100 {
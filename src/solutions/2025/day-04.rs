@@ -28,29 +28,15 @@ impl Problem<usize, usize> for Day04 {
             .count())
     }
 
-    fn part2(mut self) -> Result<usize> {
-        let mut cnt = 0;
-        loop {
-            let to_remove = self
-                .grid
-                .coordinates()
-                .filter(|c| {
-                    self.grid[*c]
-                        && Direction::all()
-                            .filter(|d| self.grid.contains(*c + *d) && self.grid[*c + *d])
-                            .count()
-                            < 4
-                })
-                .collect::<Vec<_>>();
-            if to_remove.is_empty() {
-                break;
-            }
-            cnt += to_remove.len();
-            for p in to_remove {
-                self.grid[p] = false;
-            }
-        }
-        Ok(cnt)
+    fn part2(self) -> Result<usize> {
+        let rolls = self.grid.coordinates().filter(|c| self.grid[*c]).count();
+        let mut automaton =
+            Automaton::new(self.grid, Neighborhood::Moore, |_, &roll, neighbors| {
+                roll && neighbors.iter().filter(|&&&n| n).count() >= 4
+            });
+        automaton.run_to_fixed_point();
+        let grid = automaton.state();
+        Ok(rolls - grid.coordinates().filter(|c| grid[*c]).count())
     }
 }
 
@@ -4,29 +4,18 @@ use aoc_rust::*;
 use common::*;
 
 type Point = Vec2<i64>;
-type Rect = (Point, Point);
 
 struct Day09 {
     points: Vec<Point>,
 }
 
 impl Day09 {
-    fn area((a, b): Rect) -> u64 {
-        (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
+    fn rect((a, b): (Point, Point)) -> Rect<i64> {
+        Rect::from_corners(a, b)
     }
 
-    fn is_valid(&self, rect: Rect) -> bool {
-        let (min_x, max_x) = (rect.0.x.min(rect.1.x), rect.0.x.max(rect.1.x));
-        let (min_y, max_y) = (rect.0.y.min(rect.1.y), rect.0.y.max(rect.1.y));
-
-        !self.points.iter().circular_tuple_windows().any(|(a, b)| {
-            // check if both x and y ranges intersect, i.e. the edge intersects the
-            // interior of the rectangle
-            (a.x > min_x || b.x > min_x)
-                && (a.x < max_x || b.x < max_x)
-                && (a.y > min_y || b.y > min_y)
-                && (a.y < max_y || b.y < max_y)
-        })
+    fn area(rect: Rect<i64>) -> u64 {
+        rect.area() as u64
     }
 }
 
@@ -43,18 +32,20 @@ impl Problem<u64, u64> for Day09 {
             .points
             .into_iter()
             .tuple_combinations()
-            .map(Day09::area)
+            .map(|corners| Day09::area(Day09::rect(corners)))
             .max()
             .unwrap())
     }
 
     fn part2(self) -> Result<u64> {
+        let tiles = Polygon::new(self.points.clone());
         self.points
             .iter()
             .cloned()
             .tuple_combinations()
-            .sorted_by_key(|x| Reverse(Day09::area(*x)))
-            .find(|r| self.is_valid(*r))
+            .map(Day09::rect)
+            .sorted_by_key(|&rect| Reverse(Day09::area(rect)))
+            .find(|rect| tiles.contains_rect(rect))
             .map(Day09::area)
             .ok_or(AoCError::NoSolution)
     }
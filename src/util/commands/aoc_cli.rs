@@ -66,3 +66,25 @@ pub fn download_input(date: &AocDate) -> Result<()> {
     );
     Ok(())
 }
+
+/// Submit an answer through aoc-cli, returning whether it was correct.
+pub fn submit_answer(date: &AocDate, part: u8, answer: &str) -> Result<bool> {
+    let output = Command::new("aoc")
+        .arg("submit")
+        .arg(part.to_string())
+        .arg(answer)
+        .arg("--year")
+        .arg(date.year.to_string())
+        .arg("--day")
+        .arg(date.day.to_string())
+        .output()
+        .map_err(|_| AocClientError::CommandNotFound)?;
+
+    if !output.status.success() {
+        return Err(AocClientError::BadExitStatus(output).into());
+    }
+
+    let response = String::from_utf8_lossy(&output.stdout);
+    print!("{}", response);
+    Ok(response.contains("That's the right answer"))
+}
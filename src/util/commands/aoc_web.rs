@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::{Result, CARGO_ROOT};
+
+const AOC_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/LarsvanDartel/aoc-rust";
+
+/// Read the session key, either from the `ADVENT_OF_CODE_SESSION`
+/// environment variable or from the `.session` file in the project root.
+pub fn session() -> Result<String> {
+    if let Ok(session) = std::env::var("ADVENT_OF_CODE_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+
+    let mut path = PathBuf::from(CARGO_ROOT);
+    path.push(".session");
+    let session = std::fs::read_to_string(path)
+        .map_err(|_| "No session key found, put it in the .session file")?;
+    Ok(session.trim().to_string())
+}
+
+/// Fetch a page from the Advent of Code website, authenticated with the
+/// session key.
+pub fn fetch(path: &str) -> Result<String> {
+    let session = session()?;
+    let body = ureq::get(&format!("{AOC_URL}{path}"))
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(Box::new)?
+        .into_string()?;
+    Ok(body)
+}
+
+/// Fetch a page and parse it, reusing the response cached under `name` if it
+/// is younger than `max_age`. Only responses that `parse` accepts are cached,
+/// so an error page (e.g. after the session expired) is never reused.
+pub fn fetch_cached<T, F>(path: &str, name: &str, max_age: Duration, parse: F) -> Result<T>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    let cache_path = cache_path(name)?;
+
+    let age = std::fs::metadata(&cache_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    if age.is_some_and(|age| age < max_age) {
+        if let Ok(value) = std::fs::read_to_string(&cache_path)
+            .map_err(Into::into)
+            .and_then(|body| parse(&body))
+        {
+            return Ok(value);
+        }
+    }
+
+    let body = fetch(path)?;
+    let value = parse(&body)?;
+    std::fs::write(&cache_path, &body)?;
+    Ok(value)
+}
+
+fn cache_path(name: &str) -> Result<PathBuf> {
+    let mut path = PathBuf::from(CARGO_ROOT);
+    path.push(".cache");
+    if !path.exists() {
+        std::fs::create_dir_all(&path)?;
+    }
+    path.push(name);
+    Ok(path)
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::aoc_web::fetch_cached;
+use crate::{AocDate, Result};
+
+/// Advent of Code asks to not request a private leaderboard more often than
+/// once every 15 minutes.
+const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    event: String,
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    #[serde(default)]
+    last_star_ts: i64,
+    #[serde(default)]
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+pub fn leaderboard(id: u64, year: u16) -> Result<()> {
+    let leaderboard = fetch_cached(
+        &format!("/{year}/leaderboard/private/view/{id}.json"),
+        &format!("leaderboard-{year}-{id}.json"),
+        CACHE_TIME,
+        Leaderboard::from_json,
+    )?;
+    println!("🎄 Private leaderboard {id} ({year})");
+    println!();
+    print!("{leaderboard}");
+    Ok(())
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    fn year(&self) -> u16 {
+        self.event.parse().unwrap_or_default()
+    }
+
+    /// Members ordered by local score, ties broken by who got their last star
+    /// first.
+    fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    fn days(&self) -> u8 {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Member {
+    fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn star_ts(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|parts| parts.get(&part))
+            .map(|star| star.get_star_ts)
+    }
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.days();
+        let ranked = self.ranked();
+
+        let tens = (1..=days)
+            .map(|d| {
+                if d >= 10 {
+                    char::from(b'0' + d / 10)
+                } else {
+                    ' '
+                }
+            })
+            .collect::<String>();
+        let ones = (1..=days)
+            .map(|d| char::from(b'0' + d % 10))
+            .collect::<String>();
+        writeln!(f, "{:11}{}", "", tens)?;
+        writeln!(f, "{:11}{}", "", ones)?;
+
+        for (rank, member) in ranked.iter().enumerate() {
+            let stars = (1..=days)
+                .map(|d| match (member.star_ts(d, 1), member.star_ts(d, 2)) {
+                    (Some(_), Some(_)) => '★',
+                    (Some(_), None) => '☆',
+                    _ => '·',
+                })
+                .collect::<String>();
+            writeln!(
+                f,
+                "{:>3}) {:>5} {} {:>2}★ {}",
+                rank + 1,
+                member.local_score,
+                stars,
+                member.stars,
+                member.name()
+            )?;
+        }
+
+        let width = ranked.iter().map(|m| m.name().len()).max().unwrap_or(0);
+        for day in 1..=days {
+            let release = AocDate {
+                year: self.year(),
+                day,
+            }
+            .release_timestamp();
+
+            let mut times = ranked
+                .iter()
+                .filter_map(|m| {
+                    let part1 = m.star_ts(day, 1)? - release;
+                    let part2 = m.star_ts(day, 2).map(|ts| ts - release);
+                    Some((m, part1, part2))
+                })
+                .collect::<Vec<_>>();
+            if times.is_empty() {
+                continue;
+            }
+            times.sort_by_key(|&(_, part1, part2)| (part2.unwrap_or(i64::MAX), part1));
+
+            writeln!(f)?;
+            writeln!(f, "Day {day}")?;
+            writeln!(
+                f,
+                "  {:width$}  {:>9}  {:>9}  {:>9}",
+                "", "part 1", "part 2", "delta"
+            )?;
+            for (member, part1, part2) in times {
+                writeln!(
+                    f,
+                    "  {:width$}  {:>9}  {:>9}  {:>9}",
+                    member.name(),
+                    format_duration(part1),
+                    part2.map_or_else(|| "-".to_string(), format_duration),
+                    part2.map_or_else(|| "-".to_string(), |p2| format_duration(p2 - part1)),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) fn format_duration(seconds: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        assert_eq!(leaderboard.year(), 2023);
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(leaderboard.days(), 2);
+
+        let bob = &leaderboard.members["1002"];
+        assert_eq!(bob.star_ts(1, 1), Some(1701407000));
+        assert_eq!(bob.star_ts(2, 2), None);
+    }
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let names = leaderboard
+            .ranked()
+            .into_iter()
+            .map(Member::name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Alice", "Bob", "(anonymous user #1003)"]);
+    }
+
+    #[test]
+    fn test_display() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let output = leaderboard.to_string();
+        assert!(output.contains("  1)    11 ★★  4★ Alice"));
+        assert!(output.contains("  2)     7 ★☆  3★ Bob"));
+        assert!(output.contains("  3)     1 ☆·  1★ (anonymous user #1003)"));
+        assert!(output.contains("Alice                    00:05:00   00:10:00   00:05:00"));
+        assert!(output.contains("Bob                      00:13:20          -          -"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "00:00:00");
+        assert_eq!(format_duration(3 * 3600 + 40 * 60 + 5), "03:40:05");
+        assert_eq!(format_duration(30 * 3600), "30:00:00");
+    }
+}
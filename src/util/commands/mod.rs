@@ -1,8 +1,16 @@
 pub(crate) mod aoc_cli;
+mod aoc_web;
+mod leaderboard;
 mod scaffold;
 mod solve;
+mod stats;
 mod test;
+mod timestamps;
+mod watch;
 
+pub use leaderboard::leaderboard;
 pub use scaffold::scaffold;
 pub use solve::solve;
-pub use test::test;
+pub use stats::stats;
+pub use test::{test, test_all};
+pub use watch::watch;
@@ -7,6 +7,7 @@ use toml::map::Map;
 use toml::Value;
 
 use super::aoc_cli::{check_aoc_cli, download_input};
+use super::timestamps::Timestamps;
 use crate::{AocDate, Result, CARGO_ROOT};
 
 pub fn scaffold(date: AocDate) -> Result<()> {
@@ -14,6 +15,7 @@ pub fn scaffold(date: AocDate) -> Result<()> {
     download_input(&date)?;
     create_bin(&date)?;
     add_cargo_bin(&date)?;
+    Timestamps::record_scaffold(&date)?;
     open_editor(vec![date.bin_path()?, date.input_path()?])?;
     Ok(())
 }
@@ -1,29 +1,98 @@
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::thread;
 
+use super::aoc_cli::{check_aoc_cli, submit_answer};
+use super::timestamps::Timestamps;
 use crate::{AocDate, Result};
 
-pub fn solve(date: AocDate, path: Option<PathBuf>, _submit: Option<u8>) -> Result<()> {
+/// Answers and timings of both parts, scraped from the output of a solution.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct SolveOutput {
+    pub answers: [Option<String>; 2],
+    pub times: [Option<String>; 2],
+}
+
+impl SolveOutput {
+    pub fn read_line(&mut self, line: &str) {
+        for part in 0..2 {
+            if let Some(answer) = line.strip_prefix(&format!("🎄 Task {}: ", part + 1)) {
+                self.answers[part] = Some(answer.trim_matches('"').to_string());
+            } else if let Some(time) = line.strip_prefix(&format!("🎄 Task {} took: ", part + 1))
+            {
+                self.times[part] = Some(time.to_string());
+            }
+        }
+    }
+}
+
+pub fn solve(date: AocDate, path: Option<PathBuf>, submit: Option<u8>) -> Result<()> {
     let input_path = path.unwrap_or(date.input_path()?);
     let child = thread::Builder::new()
         .name(date.bin_name().to_string())
         //.stack_size(32 * 1024 * 1024)
         .spawn(move || {
-            Command::new("cargo")
+            let mut child = Command::new("cargo")
                 .arg("run")
                 .arg("--release")
                 .arg("--bin")
                 .arg(date.bin_name())
                 .arg("--")
                 .arg(&input_path)
+                .stdout(Stdio::piped())
                 .spawn()
-                .expect("Failed to spawn child process")
-                .wait()
-                .expect("Failed to wait on child process");
+                .expect("Failed to spawn child process");
+
+            let stdout = BufReader::new(child.stdout.take().expect("Failed to capture stdout"));
+            let mut output = SolveOutput::default();
+            for line in stdout.lines().map_while(|line| line.ok()) {
+                println!("{}", line);
+                output.read_line(&line);
+            }
+
+            child.wait().expect("Failed to wait on child process");
+            output
         })?;
 
-    child.join().expect("Failed to join child thread");
+    let output = child.join().expect("Failed to join child thread");
+
+    if let Some(part) = submit {
+        let answer = match part
+            .checked_sub(1)
+            .and_then(|i| output.answers.get(i as usize))
+        {
+            Some(Some(answer)) if answer != "()" => answer,
+            Some(_) => Err("No answer to submit")?,
+            None => Err("Invalid part number")?,
+        };
+        check_aoc_cli()?;
+        println!("🎄 Submitting {} for part {}...", answer, part);
+        if submit_answer(&date, part, answer)? {
+            Timestamps::record_submission(&date, part)?;
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_output() {
+        let mut output = SolveOutput::default();
+        for line in [
+            "🎄 Running part 1...",
+            "🎄 Task 1 parsed in: 1.2µs",
+            "🎄 Task 1: 42",
+            "🎄 Task 1 took: 3.4ms",
+            "🎄 Task 2: \"ABC\"",
+        ] {
+            output.read_line(line);
+        }
+        assert_eq!(output.answers, [Some("42".into()), Some("ABC".into())]);
+        assert_eq!(output.times, [Some("3.4ms".into()), None]);
+    }
+}
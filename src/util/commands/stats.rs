@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::aoc_web::fetch_cached;
+use super::leaderboard::format_duration;
+use super::timestamps::{DayTimestamps, Timestamps};
+use crate::{AocDate, Result};
+
+const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+/// Completion time and rank of a single part, as shown on the personal times
+/// page. The time is missing when it took longer than a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct PartTime {
+    time: Option<i64>,
+    rank: Option<u32>,
+    score: Option<u32>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct DayStats {
+    day: u8,
+    part1: Option<PartTime>,
+    part2: Option<PartTime>,
+    /// Seconds between scaffolding the day and the first correct submission.
+    local_part1: Option<i64>,
+    local_part2: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    year: u16,
+    days: Vec<DayStats>,
+}
+
+pub fn stats(year: u16, json: bool) -> Result<()> {
+    let html = fetch_cached(
+        &format!("/{year}/leaderboard/self"),
+        &format!("self-{year}.html"),
+        CACHE_TIME,
+        |html| {
+            // Without a valid session this is the login page instead.
+            if html.contains("<pre>") {
+                Ok(html.to_string())
+            } else {
+                Err("No personal times found, is the session key still valid?".into())
+            }
+        },
+    )?;
+    let timestamps = Timestamps::load()?;
+    let report = Report::new(year, &html, |day| timestamps.get(&AocDate { year, day }));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("🎄 Personal stats for {year}");
+        println!();
+        print!("{report}");
+    }
+    Ok(())
+}
+
+impl Report {
+    fn new<F>(year: u16, html: &str, timestamps: F) -> Self
+    where
+        F: Fn(u8) -> DayTimestamps,
+    {
+        let mut days = parse_personal_times(html)
+            .into_iter()
+            .map(|(day, part1, part2)| {
+                (
+                    day,
+                    DayStats {
+                        day,
+                        part1,
+                        part2,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        for day in 1..=25 {
+            let local = timestamps(day);
+            let Some(scaffold) = local.scaffold else {
+                continue;
+            };
+            let stats = days.entry(day).or_insert_with(|| DayStats {
+                day,
+                ..Default::default()
+            });
+            stats.local_part1 = local.part1.map(|ts| ts - scaffold);
+            stats.local_part2 = local.part2.map(|ts| ts - scaffold);
+        }
+
+        Self {
+            year,
+            days: days.into_values().collect(),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |t: Option<i64>| t.map_or_else(|| "-".to_string(), format_duration);
+        let part_time = |p: Option<PartTime>| match p {
+            Some(PartTime { time: None, .. }) => ">24h".to_string(),
+            p => time(p.and_then(|p| p.time)),
+        };
+        let number = |n: Option<u32>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+        let rank = |p: Option<PartTime>| number(p.and_then(|p| p.rank));
+        let score = |p: Option<PartTime>| number(p.and_then(|p| p.score));
+
+        writeln!(
+            f,
+            "Day  {:>9}  {:>7}  {:>5}  {:>9}  {:>7}  {:>5}  {:>9}  {:>9}",
+            "part 1", "rank", "score", "part 2", "rank", "score", "local 1", "local 2"
+        )?;
+        for day in &self.days {
+            writeln!(
+                f,
+                "{:>3}  {:>9}  {:>7}  {:>5}  {:>9}  {:>7}  {:>5}  {:>9}  {:>9}",
+                day.day,
+                part_time(day.part1),
+                rank(day.part1),
+                score(day.part1),
+                part_time(day.part2),
+                rank(day.part2),
+                score(day.part2),
+                time(day.local_part1),
+                time(day.local_part2),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the table on `/<year>/leaderboard/self` into the completion times of
+/// both parts of every day.
+fn parse_personal_times(html: &str) -> Vec<(u8, Option<PartTime>, Option<PartTime>)> {
+    let Some(table) = html
+        .split_once("<pre>")
+        .and_then(|(_, rest)| rest.split_once("</pre>"))
+        .map(|(table, _)| table)
+    else {
+        return Vec::new();
+    };
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let line = strip_tags(line).replace("&gt;", ">");
+            let mut tokens = line.split_whitespace();
+            let day = tokens.next()?.parse().ok()?;
+            let tokens = tokens.collect::<Vec<_>>();
+            let (part1, part2) = tokens.split_at(tokens.len() / 2);
+            Some((day, parse_part_time(part1), parse_part_time(part2)))
+        })
+        .collect()
+}
+
+fn parse_part_time(tokens: &[&str]) -> Option<PartTime> {
+    let (&time, rest) = tokens.split_first()?;
+    if time == "-" {
+        return None;
+    }
+    let time = time
+        .split(':')
+        .try_fold(0, |acc, t| t.parse::<i64>().ok().map(|t| acc * 60 + t));
+    Some(PartTime {
+        time,
+        rank: rest.first().and_then(|r| r.parse().ok()),
+        score: rest.get(1).and_then(|s| s.parse().ok()),
+    })
+}
+
+fn strip_tags(line: &str) -> String {
+    let mut in_tag = false;
+    line.chars()
+        .filter(|&c| match c {
+            '<' => {
+                in_tag = true;
+                false
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                false
+            }
+            _ => !in_tag,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/self.html");
+
+    #[test]
+    fn test_parse_personal_times() {
+        let times = parse_personal_times(FIXTURE);
+        assert_eq!(times.len(), 3);
+        assert_eq!(
+            times[0],
+            (
+                3,
+                Some(PartTime {
+                    time: Some(21 * 60 + 44),
+                    rank: Some(2817),
+                    score: Some(0),
+                }),
+                Some(PartTime {
+                    time: Some(36 * 60 + 31),
+                    rank: Some(3251),
+                    score: Some(0),
+                }),
+            )
+        );
+        assert_eq!(
+            times[2],
+            (
+                1,
+                Some(PartTime {
+                    time: None,
+                    rank: Some(81234),
+                    score: Some(0),
+                }),
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report::new(2023, FIXTURE, |day| match day {
+            2 | 4 => DayTimestamps {
+                scaffold: Some(1000),
+                part1: Some(1300),
+                part2: None,
+            },
+            _ => DayTimestamps::default(),
+        });
+        assert_eq!(
+            report.days.iter().map(|d| d.day).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(report.days[1].local_part1, Some(300));
+        assert_eq!(report.days[3].part1, None);
+
+        let output = report.to_string();
+        assert!(output.contains(
+            "  1       >24h    81234      0          -        -      -          -          -"
+        ));
+        assert!(output.contains(
+            "  2   00:09:12     1024      0   00:12:40      998      0   00:05:00          -"
+        ));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["days"][0]["part1"]["score"], 0);
+        assert_eq!(json["days"][0]["part2"], serde_json::Value::Null);
+    }
+}
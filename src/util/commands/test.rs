@@ -1,7 +1,12 @@
-use std::process::Command;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
 use std::thread;
 
-use crate::{AocDate, Result};
+use toml::Value;
+
+use crate::{AocDate, Result, CARGO_ROOT};
 
 pub fn test(date: AocDate) -> Result<()> {
     let child = thread::Builder::new()
@@ -27,3 +32,206 @@ pub fn test(date: AocDate) -> Result<()> {
 
     Ok(())
 }
+
+/// Outcome of the tests of a single day.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    ignored: usize,
+    failed: Vec<String>,
+    output: String,
+}
+
+/// Run the example tests of every day of `year`, or of every day if no year
+/// is given. All days are built at once and their tests run in parallel.
+pub fn test_all(year: Option<u16>) -> Result<()> {
+    let bins = cargo_bins()?
+        .into_iter()
+        .filter(|bin| match year {
+            Some(year) => bin.starts_with(&format!("{:0>4}-", year)),
+            None => bin != "util",
+        })
+        .collect::<Vec<_>>();
+    if bins.is_empty() {
+        Err("No days found")?;
+    }
+
+    println!("🎄 Building tests for {} days...", bins.len());
+    let executables = build_tests(&bins)?;
+
+    println!("🎄 Running tests...");
+    let queue = Mutex::new(executables.into_iter());
+    let results = Mutex::new(Vec::new());
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| loop {
+                let Some((bin, executable)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let summary = match Command::new(&executable).output() {
+                    Ok(output) => {
+                        let mut summary =
+                            TestSummary::from_output(&String::from_utf8_lossy(&output.stdout));
+                        if !output.status.success() && summary.failed.is_empty() {
+                            summary.failed.push(format!("exited with {}", output.status));
+                            summary.output += &String::from_utf8_lossy(&output.stderr);
+                        }
+                        summary
+                    }
+                    Err(e) => TestSummary {
+                        failed: vec![format!("failed to run {}", executable.display())],
+                        output: e.to_string(),
+                        ..Default::default()
+                    },
+                };
+                results.lock().unwrap().push((bin, summary));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (bin, summary) in results.iter().filter(|(_, s)| !s.failed.is_empty()) {
+        println!();
+        println!("🎄 Output of {}:", bin);
+        print!("{}", summary.output);
+    }
+
+    println!();
+    for (bin, summary) in &results {
+        let mut line = format!("{} {}: {} passed", mark(summary), bin, summary.passed);
+        if summary.ignored > 0 {
+            line += &format!(", {} ignored", summary.ignored);
+        }
+        if !summary.failed.is_empty() {
+            line += &format!(
+                ", {} failed ({})",
+                summary.failed.len(),
+                summary.failed.join(", ")
+            );
+        }
+        println!("{}", line);
+    }
+
+    let failed = results.iter().filter(|(_, s)| !s.failed.is_empty()).count();
+    println!();
+    println!(
+        "🎄 {} of {} days passed",
+        results.len() - failed,
+        results.len()
+    );
+    if failed > 0 {
+        Err("Some tests failed")?;
+    }
+    Ok(())
+}
+
+fn mark(summary: &TestSummary) -> &'static str {
+    if summary.failed.is_empty() {
+        "✅"
+    } else {
+        "❌"
+    }
+}
+
+fn cargo_bins() -> Result<Vec<String>> {
+    let mut path = PathBuf::from(CARGO_ROOT);
+    path.push("Cargo.toml");
+    let cargo_toml: Value = toml::from_str(&std::fs::read_to_string(path)?)?;
+    let bins = cargo_toml
+        .get("bin")
+        .and_then(|v| v.as_array())
+        .ok_or("No bins configured? You changed and broke something.")?;
+    Ok(bins
+        .iter()
+        .filter_map(|bin| bin.get("name").and_then(|v| v.as_str()))
+        .map(|name| name.to_string())
+        .collect())
+}
+
+/// Build the test executables of all `bins` in a single cargo invocation,
+/// returning the path of the executable of each bin.
+fn build_tests(bins: &[String]) -> Result<Vec<(String, PathBuf)>> {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(CARGO_ROOT)
+        .arg("test")
+        .arg("--release")
+        .arg("--no-run")
+        .arg("--message-format=json-render-diagnostics");
+    for bin in bins {
+        cmd.arg("--bin").arg(bin);
+    }
+    let mut child = cmd.stdout(Stdio::piped()).spawn()?;
+
+    let stdout = BufReader::new(child.stdout.take().ok_or("Failed to capture stdout")?);
+    let mut executables = Vec::new();
+    for line in stdout.lines() {
+        let message: serde_json::Value = serde_json::from_str(&line?)?;
+        if message["reason"] != "compiler-artifact" || message["profile"]["test"] != true {
+            continue;
+        }
+        if let (Some(name), Some(executable)) = (
+            message["target"]["name"].as_str(),
+            message["executable"].as_str(),
+        ) {
+            executables.push((name.to_string(), PathBuf::from(executable)));
+        }
+    }
+
+    if !child.wait()?.success() {
+        Err("Failed to build tests")?;
+    }
+    Ok(executables)
+}
+
+impl TestSummary {
+    /// Summarize the output of a libtest harness.
+    fn from_output(output: &str) -> Self {
+        let mut summary = TestSummary {
+            output: output.to_string(),
+            ..Default::default()
+        };
+        for line in output.lines() {
+            let Some((name, result)) = line
+                .strip_prefix("test ")
+                .and_then(|line| line.split_once(" ... "))
+            else {
+                continue;
+            };
+            match result {
+                "ok" => summary.passed += 1,
+                "ignored" => summary.ignored += 1,
+                _ => summary.failed.push(name.to_string()),
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_from_output() {
+        let output = "
+running 3 tests
+test tests::test_part1 ... ok
+test tests::test_part2 ... FAILED
+test tests::test_slow ... ignored
+
+failures:
+
+---- tests::test_part2 stdout ----
+thread 'tests::test_part2' panicked at src/solutions/2023/day-01.rs:10:9
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+";
+        let summary = TestSummary::from_output(output);
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.ignored, 1);
+        assert_eq!(summary.failed, ["tests::test_part2"]);
+    }
+}
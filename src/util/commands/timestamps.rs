@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AocDate, Result, CARGO_ROOT};
+
+/// Locally recorded moments in the life of a day, as unix timestamps.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct DayTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scaffold: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<i64>,
+}
+
+/// The timestamps of every day, stored in `input/timestamps.toml` next to the
+/// inputs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Timestamps {
+    days: BTreeMap<String, DayTimestamps>,
+}
+
+impl Timestamps {
+    fn path() -> PathBuf {
+        let mut path = PathBuf::from(CARGO_ROOT);
+        path.push("input");
+        path.push("timestamps");
+        path.set_extension("toml");
+        path
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, date: &AocDate) -> DayTimestamps {
+        self.days.get(&date.bin_name()).copied().unwrap_or_default()
+    }
+
+    /// Record that `date` was scaffolded now, unless it was already.
+    pub fn record_scaffold(date: &AocDate) -> Result<()> {
+        Self::record(date, |day| &mut day.scaffold)
+    }
+
+    /// Record the first correct submission of `part` for `date`.
+    pub fn record_submission(date: &AocDate, part: u8) -> Result<()> {
+        match part {
+            1 => Self::record(date, |day| &mut day.part1),
+            2 => Self::record(date, |day| &mut day.part2),
+            _ => Err("Invalid part number")?,
+        }
+    }
+
+    fn record<F>(date: &AocDate, field: F) -> Result<()>
+    where
+        F: FnOnce(&mut DayTimestamps) -> &mut Option<i64>,
+    {
+        let mut timestamps = Self::load()?;
+        let day = timestamps.days.entry(date.bin_name()).or_default();
+        let field = field(day);
+        if field.is_none() {
+            *field = Some(chrono::Utc::now().timestamp());
+            timestamps.save()?;
+        }
+        Ok(())
+    }
+}
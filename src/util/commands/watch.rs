@@ -0,0 +1,110 @@
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::solve::SolveOutput;
+use crate::{AocDate, Result, CARGO_ROOT};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Rerun the example tests, and the real input once they pass, every time the
+/// source or input of the day change.
+pub fn watch(date: AocDate) -> Result<()> {
+    println!("🎄 Watching {}, press Ctrl-C to stop", date.bin_name());
+
+    let mut last_modified = None;
+    let mut previous = None;
+    loop {
+        let modified = last_modification(&date)?;
+        if modified != last_modified {
+            last_modified = modified;
+            println!();
+            if let Some(output) = run(&date)? {
+                print_diff(&output, previous.as_ref());
+                previous = Some(output);
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn last_modification(date: &AocDate) -> Result<Option<SystemTime>> {
+    Ok([date.bin_path()?, date.input_path()?]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max())
+}
+
+/// Run the example tests and, if they pass, the real input.
+fn run(date: &AocDate) -> Result<Option<SolveOutput>> {
+    println!("🎄 Running examples...");
+    let tests = Command::new("cargo")
+        .current_dir(CARGO_ROOT)
+        .arg("test")
+        .arg("--release")
+        .arg("--bin")
+        .arg(date.bin_name())
+        .arg("--")
+        .arg("--test-threads=1")
+        .output()?;
+
+    if !tests.status.success() {
+        print!("{}", String::from_utf8_lossy(&tests.stderr));
+        print!("{}", String::from_utf8_lossy(&tests.stdout));
+        println!("🎄 Examples failed");
+        return Ok(None);
+    }
+    println!("🎄 Examples passed, running input...");
+
+    let solve = Command::new("cargo")
+        .current_dir(CARGO_ROOT)
+        .arg("run")
+        .arg("--release")
+        .arg("--bin")
+        .arg(date.bin_name())
+        .arg("--")
+        .arg(date.input_path()?)
+        .output()?;
+
+    if !solve.status.success() {
+        print!("{}", String::from_utf8_lossy(&solve.stderr));
+        println!("🎄 Solution failed");
+        return Ok(None);
+    }
+
+    let mut output = SolveOutput::default();
+    for line in String::from_utf8_lossy(&solve.stdout).lines() {
+        output.read_line(line);
+    }
+    Ok(Some(output))
+}
+
+fn print_diff(output: &SolveOutput, previous: Option<&SolveOutput>) {
+    for part in 0..2 {
+        let answer = output.answers[part].as_deref().unwrap_or("-");
+        let time = output.times[part].as_deref().unwrap_or("-");
+        let (prev_answer, prev_time) = match previous {
+            Some(previous) => (
+                previous.answers[part].as_deref().unwrap_or("-"),
+                previous.times[part].as_deref().unwrap_or("-"),
+            ),
+            None => {
+                println!("🎄 Part {}: {} in {}", part + 1, answer, time);
+                continue;
+            }
+        };
+
+        let answer = if answer == prev_answer {
+            answer.to_string()
+        } else {
+            format!("{} (was {})", answer, prev_answer)
+        };
+        println!(
+            "🎄 Part {}: {} in {} (was {})",
+            part + 1,
+            answer,
+            time,
+            prev_time
+        );
+    }
+}
@@ -8,6 +8,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub(crate) enum Error {
     AocClient(AocClientError),
     Io(std::io::Error),
+    Http(Box<ureq::Error>),
+    Json(serde_json::Error),
     TomlDeserialize(toml::de::Error),
     TomlSerialize(toml::ser::Error),
     InvalidDate(AocDate),
@@ -27,6 +29,18 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<Box<ureq::Error>> for Error {
+    fn from(e: Box<ureq::Error>) -> Self {
+        Self::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
 impl From<toml::de::Error> for Error {
     fn from(e: toml::de::Error) -> Self {
         Self::TomlDeserialize(e)
@@ -3,7 +3,7 @@ mod error;
 
 use std::path::PathBuf;
 
-use chrono::{Datelike, FixedOffset};
+use chrono::{Datelike, FixedOffset, TimeZone};
 use clap::Parser;
 pub(crate) use error::Result;
 
@@ -35,12 +35,41 @@ enum Args {
         submit: Option<u8>,
     },
 
+    /// Run the examples of a single day, of a whole year when only the year
+    /// is given, or of every day with `--all`.
     #[clap(name = "test")]
     Test {
         #[clap(short, long)]
         year: Option<u16>,
         #[clap(short, long)]
         day: Option<u8>,
+        #[clap(long, conflicts_with_all = ["year", "day"])]
+        all: bool,
+    },
+
+    #[clap(name = "watch")]
+    Watch {
+        #[clap(short, long)]
+        year: Option<u16>,
+        #[clap(short, long)]
+        day: Option<u8>,
+    },
+
+    #[clap(name = "leaderboard")]
+    Leaderboard {
+        id: u64,
+
+        #[clap(short, long)]
+        year: Option<u16>,
+    },
+
+    #[clap(name = "stats")]
+    Stats {
+        #[clap(short, long)]
+        year: Option<u16>,
+
+        #[clap(long)]
+        json: bool,
     },
 }
 
@@ -51,10 +80,13 @@ struct AocDate {
 }
 
 impl AocDate {
-    fn recent() -> Self {
+    fn timezone() -> FixedOffset {
         // aoc is based in UTC-5
-        let tz = FixedOffset::west_opt(5 * 3600).unwrap();
-        let now = chrono::Utc::now().with_timezone(&tz);
+        FixedOffset::west_opt(5 * 3600).unwrap()
+    }
+
+    fn recent() -> Self {
+        let now = chrono::Utc::now().with_timezone(&Self::timezone());
 
         let year = match now.month() {
             12 => now.year(),
@@ -69,6 +101,14 @@ impl AocDate {
         Self { year, day }
     }
 
+    /// Unix timestamp at which the puzzle unlocks.
+    fn release_timestamp(&self) -> i64 {
+        Self::timezone()
+            .with_ymd_and_hms(self.year as i32, 12, self.day as u32, 0, 0, 0)
+            .unwrap()
+            .timestamp()
+    }
+
     fn input_path(&self) -> Result<PathBuf> {
         let mut path = PathBuf::from(CARGO_ROOT);
         path.push("input");
@@ -148,7 +188,19 @@ fn main() -> Result<()> {
             date.check_date()?;
             commands::solve(date, path, submit)?;
         }
-        Args::Test { year, day } => {
+        Args::Test { all: true, .. } => {
+            commands::test_all(None)?;
+        }
+        Args::Test {
+            year: Some(year),
+            day: None,
+            ..
+        } => {
+            date.year = cleanup_year(year);
+            date.check_date()?;
+            commands::test_all(Some(date.year))?;
+        }
+        Args::Test { year, day, .. } => {
             if let Some(year) = year {
                 date.year = cleanup_year(year);
             }
@@ -158,6 +210,30 @@ fn main() -> Result<()> {
             date.check_date()?;
             commands::test(date)?;
         }
+        Args::Watch { year, day } => {
+            if let Some(year) = year {
+                date.year = cleanup_year(year);
+            }
+            if let Some(day) = day {
+                date.day = day;
+            }
+            date.check_date()?;
+            commands::watch(date)?;
+        }
+        Args::Leaderboard { id, year } => {
+            if let Some(year) = year {
+                date.year = cleanup_year(year);
+            }
+            date.check_date()?;
+            commands::leaderboard(id, date.year)?;
+        }
+        Args::Stats { year, json } => {
+            if let Some(year) = year {
+                date.year = cleanup_year(year);
+            }
+            date.check_date()?;
+            commands::stats(date.year, json)?;
+        }
     }
 
     Ok(())